@@ -0,0 +1,893 @@
+//! Derive macros for `push_decode`.
+//!
+//! This crate is not supposed to be used directly, use the `derive` feature of `push_decode`
+//! instead.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::{Data, DeriveInput, Error, Expr, Fields, GenericArgument, Ident, LitStr, Member, PathArguments, Type, Visibility};
+
+/// The maximum number of bytes reserved upfront for length-prefixed fields.
+///
+/// The length is untrusted so reserving all of it could exhaust memory before any data arrives.
+const RESERVE_LIMIT: usize = 4096;
+
+/// Derives `Decode` for a struct or an enum generating a decoder and an error type.
+///
+/// See the documentation of `push_decode` for details.
+#[proc_macro_derive(Decode, attributes(push_decode))]
+pub fn derive_decode(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as DeriveInput);
+    Input::parse(&input)
+        .map(|input| input.decode())
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Derives `Encode` for a reference to a struct or an enum generating an encoder.
+///
+/// See the documentation of `push_decode` for details.
+#[proc_macro_derive(Encode, attributes(push_decode))]
+pub fn derive_encode(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as DeriveInput);
+    Input::parse(&input)
+        .map(|input| input.encode())
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+struct Input {
+    name: Ident,
+    vis: Visibility,
+    kind: InputKind,
+}
+
+enum InputKind {
+    Struct(Vec<Field>),
+    Enum { tag_type: Box<Type>, variants: Vec<Variant> },
+}
+
+struct Variant {
+    name: Ident,
+    tag: Expr,
+    fields: Vec<Field>,
+}
+
+struct Field {
+    /// Used for state and error variant names.
+    step: Ident,
+    /// Used to store the decoded value in the decoder.
+    storage: Ident,
+    member: Member,
+    ty: Type,
+    kind: FieldKind,
+    description: String,
+    /// The field whose length is stored in this field.
+    len_of: Option<Member>,
+}
+
+enum FieldKind {
+    Default,
+    LittleEndian,
+    BigEndian,
+    Len { storage: Ident, target: LenTarget },
+}
+
+enum LenTarget {
+    Bytes,
+    String,
+}
+
+#[derive(Default)]
+struct FieldAttrs {
+    endian: Option<LitStr>,
+    len: Option<LitStr>,
+}
+
+impl FieldAttrs {
+    fn parse(attrs: &[syn::Attribute]) -> syn::Result<Self> {
+        let mut result = FieldAttrs::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("push_decode")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("endian") {
+                    result.endian = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("len") {
+                    result.len = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else {
+                    Err(meta.error("unknown field attribute, expected `endian` or `len`"))
+                }
+            })?;
+        }
+        Ok(result)
+    }
+}
+
+fn to_camel_case(name: &str) -> String {
+    name.split('_')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect::<String>(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+fn len_target(ty: &Type) -> Option<LenTarget> {
+    let last = match ty {
+        Type::Path(path) if path.qself.is_none() => path.path.segments.last()?,
+        _ => return None,
+    };
+    match &last.arguments {
+        PathArguments::None if last.ident == "String" => Some(LenTarget::String),
+        PathArguments::AngleBracketed(args) if last.ident == "Vec" && args.args.len() == 1 => match &args.args[0] {
+            GenericArgument::Type(Type::Path(arg)) if arg.qself.is_none() && arg.path.is_ident("u8") => Some(LenTarget::Bytes),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn parse_fields(fields: &Fields, prefix: Option<&Ident>) -> syn::Result<Vec<Field>> {
+    let mut result = Vec::<Field>::with_capacity(fields.len());
+    for (i, field) in fields.iter().enumerate() {
+        let (member, name) = match &field.ident {
+            Some(ident) => (Member::Named(ident.clone()), ident.to_string()),
+            None => (Member::Unnamed(i.into()), i.to_string()),
+        };
+        let (step, storage, description) = match prefix {
+            Some(variant) => (
+                format_ident!("{}{}", variant, if field.ident.is_some() { to_camel_case(&name) } else { name.clone() }),
+                format_ident!("variant_{}_{}", variant, name),
+                format!("field `{}` of variant `{}`", name, variant),
+            ),
+            None => (
+                if field.ident.is_some() { format_ident!("{}", to_camel_case(&name)) } else { format_ident!("Field{}", name) },
+                format_ident!("field_{}", name),
+                format!("field `{}`", name),
+            ),
+        };
+        let attrs = FieldAttrs::parse(&field.attrs)?;
+        let kind = match (attrs.endian, attrs.len) {
+            (Some(_), Some(len)) => return Err(Error::new(len.span(), "`endian` and `len` can not be combined")),
+            (Some(endian), None) => match &*endian.value() {
+                "le" | "little" => FieldKind::LittleEndian,
+                "be" | "big" => FieldKind::BigEndian,
+                _ => return Err(Error::new(endian.span(), "unknown endianness, expected `le` or `be`")),
+            },
+            (None, Some(len)) => {
+                let target = len_target(&field.ty)
+                    .ok_or_else(|| Error::new(len.span(), "`len` is only supported on `Vec<u8>` and `String`"))?;
+                let len_field = result.iter_mut()
+                    .find(|field| match &field.member {
+                        Member::Named(ident) => *ident == len.value(),
+                        Member::Unnamed(index) => index.index.to_string() == len.value(),
+                    })
+                    .ok_or_else(|| Error::new(len.span(), "the length field must be declared before this field"))?;
+                if len_field.len_of.is_some() {
+                    return Err(Error::new(len.span(), "the length field already stores the length of another field"));
+                }
+                len_field.len_of = Some(member.clone());
+                // Integers decode as big endian by default, the concrete decoder allows the length
+                // to be known as soon as it's decoded.
+                if let FieldKind::Default = len_field.kind {
+                    len_field.kind = FieldKind::BigEndian;
+                }
+                FieldKind::Len { storage: len_field.storage.clone(), target }
+            },
+            (None, None) => FieldKind::Default,
+        };
+        result.push(Field {
+            step,
+            storage,
+            member,
+            ty: field.ty.clone(),
+            kind,
+            description,
+            len_of: None,
+        });
+    }
+    Ok(result)
+}
+
+impl Input {
+    fn parse(input: &DeriveInput) -> syn::Result<Self> {
+        if !input.generics.params.is_empty() {
+            return Err(Error::new_spanned(&input.generics, "generic types are not supported"));
+        }
+        let mut tag_type = None;
+        for attr in input.attrs.iter().filter(|attr| attr.path().is_ident("push_decode")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("tag_type") {
+                    let ty = meta.value()?.parse::<LitStr>()?;
+                    tag_type = Some(ty.parse::<Type>()?);
+                    Ok(())
+                } else {
+                    Err(meta.error("unknown container attribute, expected `tag_type`"))
+                }
+            })?;
+        }
+        let kind = match &input.data {
+            Data::Struct(data) => {
+                if let Some(tag_type) = tag_type {
+                    return Err(Error::new_spanned(tag_type, "`tag_type` is only supported on enums"));
+                }
+                InputKind::Struct(parse_fields(&data.fields, None)?)
+            },
+            Data::Enum(data) => {
+                if data.variants.is_empty() {
+                    return Err(Error::new_spanned(&input.ident, "enums without variants are not supported"));
+                }
+                let mut variants = Vec::with_capacity(data.variants.len());
+                for (i, variant) in data.variants.iter().enumerate() {
+                    let mut tag = variant.discriminant.as_ref().map(|(_, discriminant)| discriminant.clone());
+                    for attr in variant.attrs.iter().filter(|attr| attr.path().is_ident("push_decode")) {
+                        attr.parse_nested_meta(|meta| {
+                            if meta.path.is_ident("tag") {
+                                tag = Some(meta.value()?.parse()?);
+                                Ok(())
+                            } else {
+                                Err(meta.error("unknown variant attribute, expected `tag`"))
+                            }
+                        })?;
+                    }
+                    let tag = tag.unwrap_or_else(|| {
+                        let index = syn::LitInt::new(&i.to_string(), Span::call_site());
+                        syn::parse_quote!(#index)
+                    });
+                    variants.push(Variant {
+                        name: variant.ident.clone(),
+                        tag,
+                        fields: parse_fields(&variant.fields, Some(&variant.ident))?,
+                    });
+                }
+                InputKind::Enum {
+                    tag_type: Box::new(tag_type.unwrap_or_else(|| syn::parse_quote!(u8))),
+                    variants,
+                }
+            },
+            Data::Union(data) => return Err(Error::new_spanned(data.union_token, "unions are not supported")),
+        };
+        Ok(Input {
+            name: input.ident.clone(),
+            vis: input.vis.clone(),
+            kind,
+        })
+    }
+
+    fn fields(&self) -> Box<dyn Iterator<Item = &Field> + '_> {
+        match &self.kind {
+            InputKind::Struct(fields) => Box::new(fields.iter()),
+            InputKind::Enum { variants, .. } => Box::new(variants.iter().flat_map(|variant| &variant.fields)),
+        }
+    }
+
+    fn decode(&self) -> TokenStream2 {
+        let name = &self.name;
+        let vis = &self.vis;
+        let decoder = format_ident!("{}Decoder", name);
+        let state = format_ident!("{}DecoderState", name);
+        let error = format_ident!("{}DecodeError", name);
+        let decoder_doc = format!("Decoder of [`{}`] generated by `#[derive(Decode)]`.", name);
+        let error_doc = format!("Error returned when decoding [`{}`] fails.", name);
+
+        let steps = self.fields().map(|field| &field.step).collect::<Vec<_>>();
+        let storages = self.fields().map(|field| &field.storage).collect::<Vec<_>>();
+        let types = self.fields().map(|field| &field.ty).collect::<Vec<_>>();
+        let decoder_types = self.fields().map(Field::decoder_type).collect::<Vec<_>>();
+        let error_docs = self.fields().map(|field| format!("Decoding {} failed.", field.description)).collect::<Vec<_>>();
+        let error_messages = self.fields().map(|field| format!("failed to decode {}", field.description)).collect::<Vec<_>>();
+        let decode_arms = self.fields().map(|field| {
+            let step = &field.step;
+            let complete = if field.has_known_min_len() {
+                quote!(::push_decode::KnownMinLenDecoder::min_required_bytes(decoder) == 0)
+            } else {
+                quote!(false)
+            };
+            quote! {
+                #state::#step(decoder) => {
+                    ::push_decode::Decoder::decode_chunk(decoder, bytes).map_err(#error::#step)?;
+                    #complete
+                },
+            }
+        }).collect::<TokenStream2>();
+
+        let (tag_state, tag_storage, tag_init, tag_decode, tag_min_len, tag_error, tag_display, tag_source, first_state, transitions, construct, min_lens) = match &self.kind {
+            InputKind::Struct(fields) => {
+                let first_state = state_after(&state, fields, 0);
+                let transitions = fields.iter().enumerate().map(|(i, field)| {
+                    let step = &field.step;
+                    let storage = &field.storage;
+                    let next = state_after(&state, fields, i + 1);
+                    quote! {
+                        #state::#step(decoder) => {
+                            self.#storage = ::core::option::Option::Some(::push_decode::Decoder::end(decoder).map_err(#error::#step)?);
+                            self.state = #next;
+                        },
+                    }
+                }).collect::<TokenStream2>();
+                let members = fields.iter().map(|field| &field.member);
+                let storages = fields.iter().map(|field| &field.storage);
+                let construct = quote! {
+                    #name { #(#members: self.#storages.unwrap(),)* }
+                };
+                let min_lens = min_lens(&state, fields);
+                (quote!(), quote!(), quote!(), quote!(), quote!(), quote!(), quote!(), quote!(), first_state, transitions, construct, min_lens)
+            },
+            InputKind::Enum { tag_type, variants } => {
+                // The concrete decoder allows selecting the variant as soon as the tag is complete.
+                let tag_decoder = quote!(::push_decode::decoders::IntDecoder<#tag_type, ::push_decode::int::BigEndian>);
+                let tag_state = quote! {
+                    Tag(#tag_decoder),
+                };
+                let tag_storage = quote! {
+                    tag: ::core::option::Option<#tag_type>,
+                };
+                let tag_init = quote! {
+                    tag: ::core::option::Option::None,
+                };
+                let tag_decode = quote! {
+                    #state::Tag(decoder) => {
+                        ::push_decode::Decoder::decode_chunk(decoder, bytes).map_err(#error::Tag)?;
+                        ::push_decode::KnownMinLenDecoder::min_required_bytes(decoder) == 0
+                    },
+                };
+                let tag_min_len = quote! {
+                    #state::Tag(decoder) => ::push_decode::KnownMinLenDecoder::min_required_bytes(decoder),
+                };
+                let tag_source = quote! {
+                    #error::Tag(ref error) => ::core::option::Option::Some(error),
+                    #error::UnknownTag(_) => ::core::option::Option::None,
+                };
+                let tag_error = quote! {
+                    /// Decoding the tag failed.
+                    Tag(<#tag_decoder as ::push_decode::Decoder>::Error),
+                    /// The tag doesn't match any variant.
+                    UnknownTag(#tag_type),
+                };
+                let tag_display = quote! {
+                    #error::Tag(_) => f.write_str("failed to decode the tag"),
+                    #error::UnknownTag(ref tag) => write!(f, "unknown tag {}", tag),
+                };
+                let first_state = quote!(#state::Tag(::core::default::Default::default()));
+                let tag_arms = variants.iter().map(|variant| {
+                    let tag = &variant.tag;
+                    let next = state_after(&state, &variant.fields, 0);
+                    quote! {
+                        tag if tag == (#tag) => #next,
+                    }
+                });
+                let mut transitions = quote! {
+                    #state::Tag(decoder) => {
+                        let tag = ::push_decode::Decoder::end(decoder).map_err(#error::Tag)?;
+                        self.tag = ::core::option::Option::Some(tag);
+                        self.state = match tag {
+                            #(#tag_arms)*
+                            tag => return ::core::result::Result::Err(#error::UnknownTag(tag)),
+                        };
+                    },
+                };
+                for variant in variants {
+                    for (i, field) in variant.fields.iter().enumerate() {
+                        let step = &field.step;
+                        let storage = &field.storage;
+                        let next = state_after(&state, &variant.fields, i + 1);
+                        transitions.extend(quote! {
+                            #state::#step(decoder) => {
+                                self.#storage = ::core::option::Option::Some(::push_decode::Decoder::end(decoder).map_err(#error::#step)?);
+                                self.state = #next;
+                            },
+                        });
+                    }
+                }
+                let construct_arms = variants.iter().map(|variant| {
+                    let tag = &variant.tag;
+                    let variant_name = &variant.name;
+                    let members = variant.fields.iter().map(|field| &field.member);
+                    let storages = variant.fields.iter().map(|field| &field.storage);
+                    quote! {
+                        tag if tag == (#tag) => #name::#variant_name { #(#members: self.#storages.unwrap(),)* },
+                    }
+                });
+                let construct = quote! {
+                    match self.tag.unwrap() {
+                        #(#construct_arms)*
+                        _ => unreachable!("the tag was checked"),
+                    }
+                };
+                let min_lens = variants.iter().map(|variant| min_lens(&state, &variant.fields)).collect();
+                (tag_state, tag_storage, tag_init, tag_decode, tag_min_len, tag_error, tag_display, tag_source, first_state, transitions, construct, min_lens)
+            },
+        };
+
+        quote! {
+            #[doc = #decoder_doc]
+            #[allow(non_snake_case)]
+            #vis struct #decoder {
+                state: #state,
+                #tag_storage
+                #(#storages: ::core::option::Option<#types>,)*
+            }
+
+            #[allow(non_camel_case_types)]
+            enum #state {
+                #tag_state
+                #(#steps(#decoder_types),)*
+                Done,
+                Invalid,
+            }
+
+            #[doc = #error_doc]
+            #[derive(Debug)]
+            #[allow(non_camel_case_types)]
+            #vis enum #error {
+                #tag_error
+                #(
+                    #[doc = #error_docs]
+                    #steps(<#decoder_types as ::push_decode::Decoder>::Error),
+                )*
+            }
+
+            impl ::core::fmt::Display for #error {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                    match *self {
+                        #tag_display
+                        #(#error::#steps(_) => f.write_str(#error_messages),)*
+                    }
+                }
+            }
+
+            ::push_decode::__std_only! {
+                impl ::push_decode::__private::StdError for #error where #(for<'a> <#decoder_types as ::push_decode::Decoder>::Error: ::push_decode::__private::StdError + 'static,)* {
+                    fn source(&self) -> ::core::option::Option<&(dyn ::push_decode::__private::StdError + 'static)> {
+                        match *self {
+                            #tag_source
+                            #(#error::#steps(ref error) => ::core::option::Option::Some(error),)*
+                        }
+                    }
+                }
+            }
+
+            impl #decoder {
+                /// Creates the decoder.
+                #vis fn new() -> Self {
+                    #decoder {
+                        state: #first_state,
+                        #tag_init
+                        #(#storages: ::core::option::Option::None,)*
+                    }
+                }
+
+                fn advance(&mut self) -> ::core::result::Result<(), #error> {
+                    match ::core::mem::replace(&mut self.state, #state::Invalid) {
+                        #transitions
+                        #state::Done => self.state = #state::Done,
+                        #state::Invalid => panic!("use of failed decoder"),
+                    }
+                    ::core::result::Result::Ok(())
+                }
+            }
+
+            impl ::core::default::Default for #decoder {
+                fn default() -> Self {
+                    Self::new()
+                }
+            }
+
+            impl ::push_decode::Decoder for #decoder {
+                type Value = #name;
+                type Error = #error;
+
+                // unreachable for types without fields
+                #[allow(unreachable_code)]
+                fn decode_chunk(&mut self, bytes: &mut &[u8]) -> ::core::result::Result<(), Self::Error> {
+                    loop {
+                        // Decoders known to be complete are advanced right away so that
+                        // `min_required_bytes` doesn't need to look into the following fields.
+                        let complete: bool = match &mut self.state {
+                            #tag_decode
+                            #decode_arms
+                            #state::Done => return ::core::result::Result::Ok(()),
+                            #state::Invalid => panic!("use of failed decoder"),
+                        };
+                        if bytes.is_empty() && !complete {
+                            return ::core::result::Result::Ok(());
+                        }
+                        self.advance()?;
+                    }
+                }
+
+                fn end(mut self) -> ::core::result::Result<Self::Value, Self::Error> {
+                    while !::core::matches!(self.state, #state::Done) {
+                        self.advance()?;
+                    }
+                    ::core::result::Result::Ok(#construct)
+                }
+            }
+
+            // The higher-ranked bounds make the impl conditional instead of failing to compile if
+            // some decoder doesn't implement the trait.
+            impl ::push_decode::KnownMinLenDecoder for #decoder where #(for<'a> #decoder_types: ::push_decode::KnownMinLenDecoder,)* {
+                fn min_required_bytes(&self) -> usize {
+                    match &self.state {
+                        #tag_min_len
+                        #min_lens
+                        #state::Done => 0,
+                        #state::Invalid => panic!("use of failed decoder"),
+                    }
+                }
+            }
+
+            impl ::push_decode::Decode for #name {
+                type Decoder = #decoder;
+            }
+        }
+    }
+
+    fn encode(&self) -> TokenStream2 {
+        let name = &self.name;
+        let vis = &self.vis;
+        let encoder = format_ident!("{}Encoder", name);
+        let state = format_ident!("{}EncoderState", name);
+        let encoder_doc = format!("Encoder of [`{}`] generated by `#[derive(Encode)]`.", name);
+
+        let steps = self.fields().map(|field| &field.step).collect::<Vec<_>>();
+        let encoder_types = self.fields().map(Field::encoder_type).collect::<Vec<_>>();
+        let len_checks = match &self.kind {
+            InputKind::Struct(fields) => len_checks(quote!(#name), fields, false),
+            InputKind::Enum { variants, .. } => variants.iter()
+                .map(|variant| {
+                    let variant_name = &variant.name;
+                    len_checks(quote!(#name::#variant_name), &variant.fields, true)
+                })
+                .collect(),
+        };
+
+        let (tag_state, tag_chunk, tag_next, first_state, transitions) = match &self.kind {
+            InputKind::Struct(fields) => {
+                let first_state = encoder_state_after(&state, fields, 0, |field| {
+                    let member = &field.member;
+                    quote!(&value.#member)
+                });
+                let transitions = fields.iter().enumerate().map(|(i, field)| {
+                    let step = &field.step;
+                    let next = encoder_state_after(&state, fields, i + 1, |field| {
+                        let member = &field.member;
+                        quote!(&value.#member)
+                    });
+                    quote! {
+                        #state::#step(_) => #next,
+                    }
+                }).collect::<TokenStream2>();
+                (quote!(), quote!(), quote!(), first_state, transitions)
+            },
+            InputKind::Enum { tag_type, variants } => {
+                let tag_state = quote! {
+                    Tag(<#tag_type as ::push_decode::Encode>::Encoder),
+                };
+                let tag_arms = variants.iter().map(|variant| {
+                    let variant_name = &variant.name;
+                    let tag = &variant.tag;
+                    quote! {
+                        #name::#variant_name { .. } => {
+                            let tag: #tag_type = #tag;
+                            #state::Tag(::push_decode::Encode::encoder(tag))
+                        },
+                    }
+                });
+                let first_state = quote! {
+                    match value {
+                        #(#tag_arms)*
+                    }
+                };
+                let field_accessor = |variant: &Variant| {
+                    let variant_name = variant.name.clone();
+                    move |field: &Field| {
+                        let member = &field.member;
+                        quote! {
+                            match value {
+                                #name::#variant_name { #member: field, .. } => field,
+                                _ => unreachable!("the variant doesn't change"),
+                            }
+                        }
+                    }
+                };
+                let after_tag_arms = variants.iter().map(|variant| {
+                    let variant_name = &variant.name;
+                    let next = encoder_state_after(&state, &variant.fields, 0, field_accessor(variant));
+                    quote! {
+                        #name::#variant_name { .. } => #next,
+                    }
+                });
+                let mut transitions = quote! {
+                    #state::Tag(_) => match value {
+                        #(#after_tag_arms)*
+                    },
+                };
+                for variant in variants {
+                    for (i, field) in variant.fields.iter().enumerate() {
+                        let step = &field.step;
+                        let next = encoder_state_after(&state, &variant.fields, i + 1, field_accessor(variant));
+                        transitions.extend(quote! {
+                            #state::#step(_) => #next,
+                        });
+                    }
+                }
+                let tag_chunk = quote! {
+                    #state::Tag(encoder) => ::push_decode::Encoder::encoded_chunk(encoder),
+                };
+                let tag_next = quote! {
+                    #state::Tag(encoder) => ::push_decode::Encoder::next(encoder),
+                };
+                (tag_state, tag_chunk, tag_next, first_state, transitions)
+            },
+        };
+
+        let init = quote! {
+            let mut encoder = #encoder {
+                value,
+                state: #first_state,
+            };
+            encoder.skip_empty();
+            encoder
+        };
+        // The length fields are encoded from the lengths of the values which may not fit.
+        let (constructor, encode_impl) = if self.fields().any(|field| field.len_of.is_some()) {
+            let constructor = quote! {
+                /// Creates the encoder.
+                ///
+                /// # Errors
+                ///
+                /// Returns an error if the length of a field doesn't fit into its length field.
+                #vis fn new(value: &'a #name) -> ::core::result::Result<Self, ::push_decode::error::LengthOverflow> {
+                    #len_checks
+                    ::core::result::Result::Ok({ #init })
+                }
+            };
+            (constructor, quote!())
+        } else {
+            let constructor = quote! {
+                /// Creates the encoder.
+                #vis fn new(value: &'a #name) -> Self {
+                    #init
+                }
+            };
+            let encode_impl = quote! {
+                impl<'a> ::push_decode::Encode for &'a #name {
+                    type Encoder = #encoder<'a>;
+
+                    fn encoder(self) -> Self::Encoder {
+                        #encoder::new(self)
+                    }
+                }
+            };
+            (constructor, encode_impl)
+        };
+
+        quote! {
+            #[doc = #encoder_doc]
+            #vis struct #encoder<'a> {
+                value: &'a #name,
+                state: #state<'a>,
+            }
+
+            #[allow(non_camel_case_types)]
+            enum #state<'a> {
+                #tag_state
+                #(#steps(#encoder_types),)*
+                Done(::core::marker::PhantomData<&'a ()>),
+            }
+
+            impl<'a> #encoder<'a> {
+                #constructor
+
+                #[allow(unused_variables)]
+                fn advance(&mut self) {
+                    let value: &'a #name = self.value;
+                    self.state = match &self.state {
+                        #transitions
+                        #state::Done(_) => #state::Done(::core::marker::PhantomData),
+                    };
+                }
+
+                fn skip_empty(&mut self) {
+                    while !::core::matches!(self.state, #state::Done(_)) && ::push_decode::Encoder::encoded_chunk(self).is_empty() {
+                        self.advance();
+                    }
+                }
+            }
+
+            impl<'a> ::push_decode::Encoder for #encoder<'a> {
+                fn encoded_chunk(&self) -> &[u8] {
+                    match &self.state {
+                        #state::Done(_) => &[],
+                        #tag_chunk
+                        #(#state::#steps(encoder) => ::push_decode::Encoder::encoded_chunk(encoder),)*
+                    }
+                }
+
+                // unreachable for types without fields
+                #[allow(unreachable_code)]
+                fn next(&mut self) -> bool {
+                    let has_next = match &mut self.state {
+                        #state::Done(_) => return false,
+                        #tag_next
+                        #(#state::#steps(encoder) => ::push_decode::Encoder::next(encoder),)*
+                    };
+                    if has_next {
+                        return true;
+                    }
+                    self.advance();
+                    self.skip_empty();
+                    !::core::matches!(self.state, #state::Done(_))
+                }
+            }
+
+            #encode_impl
+        }
+    }
+}
+
+impl Field {
+    fn decoder_type(&self) -> TokenStream2 {
+        let ty = &self.ty;
+        match &self.kind {
+            FieldKind::Default => quote!(<#ty as ::push_decode::Decode>::Decoder),
+            FieldKind::LittleEndian => quote!(::push_decode::decoders::IntDecoder<#ty, ::push_decode::int::LittleEndian>),
+            FieldKind::BigEndian => quote!(::push_decode::decoders::IntDecoder<#ty, ::push_decode::int::BigEndian>),
+            FieldKind::Len { target: LenTarget::Bytes, .. } => quote!(::push_decode::decoders::ByteVecDecoder),
+            FieldKind::Len { target: LenTarget::String, .. } => quote!(::push_decode::decoders::Utf8StringDecoder),
+        }
+    }
+
+    fn decoder(&self) -> TokenStream2 {
+        match &self.kind {
+            FieldKind::Default | FieldKind::LittleEndian | FieldKind::BigEndian => quote!(::core::default::Default::default()),
+            FieldKind::Len { storage, target, .. } => {
+                let constructor = match target {
+                    LenTarget::Bytes => quote!(::push_decode::decoders::ByteVecDecoder::with_reserve_limit(len, #RESERVE_LIMIT)),
+                    LenTarget::String => quote!(::push_decode::decoders::Utf8StringDecoder::with_reserve_limit(len, #RESERVE_LIMIT)),
+                };
+                quote! {
+                    {
+                        let len: usize = match &self.#storage {
+                            ::core::option::Option::Some(len) => ::core::convert::TryFrom::try_from(*len).unwrap_or(usize::MAX),
+                            ::core::option::Option::None => unreachable!("the length is decoded first"),
+                        };
+                        #constructor
+                    }
+                }
+            },
+        }
+    }
+
+    /// Whether the decoder is known to implement `KnownMinLenDecoder`.
+    fn has_known_min_len(&self) -> bool {
+        !matches!(self.kind, FieldKind::Default)
+    }
+
+    /// Returns the minimum number of bytes required to decode the field before its decoder is
+    /// created.
+    fn min_required_bytes(&self) -> TokenStream2 {
+        match &self.kind {
+            FieldKind::Len { storage, .. } => quote! {
+                match &self.#storage {
+                    ::core::option::Option::Some(len) => ::core::convert::TryFrom::try_from(*len).unwrap_or(usize::MAX),
+                    // the length field wasn't decoded yet and requires some bytes
+                    ::core::option::Option::None => 0,
+                }
+            },
+            _ => {
+                let decoder_type = self.decoder_type();
+                quote!(::push_decode::KnownMinLenDecoder::min_required_bytes(&<#decoder_type as ::core::default::Default>::default()))
+            },
+        }
+    }
+
+    fn encoder_type(&self) -> TokenStream2 {
+        let ty = &self.ty;
+        match &self.kind {
+            FieldKind::Default => quote!(<&'a #ty as ::push_decode::Encode>::Encoder),
+            FieldKind::LittleEndian | FieldKind::BigEndian => quote!(::push_decode::encoders::IntEncoder<#ty>),
+            FieldKind::Len { .. } => quote!(::push_decode::encoders::BytesEncoder<&'a #ty>),
+        }
+    }
+
+    fn encoder(&self, field: TokenStream2) -> TokenStream2 {
+        match &self.kind {
+            FieldKind::Default => quote!(::push_decode::Encode::encoder(#field)),
+            FieldKind::LittleEndian => quote!(::push_decode::encoders::IntEncoder::new_le(*#field)),
+            FieldKind::BigEndian => quote!(::push_decode::encoders::IntEncoder::new_be(*#field)),
+            FieldKind::Len { .. } => quote!(::push_decode::encoders::BytesEncoder::new(#field)),
+        }
+    }
+
+    /// Returns the encoder of a length field encoding the length of `target`.
+    fn len_encoder(&self, target: TokenStream2) -> TokenStream2 {
+        let ty = &self.ty;
+        let constructor = match &self.kind {
+            FieldKind::LittleEndian => quote!(new_le),
+            _ => quote!(new_be),
+        };
+        quote! {
+            {
+                let len: #ty = ::core::convert::TryFrom::try_from((#target).len())
+                    .unwrap_or_else(|_| unreachable!("the length was checked in `new`"));
+                ::push_decode::encoders::IntEncoder::#constructor(len)
+            }
+        }
+    }
+}
+
+/// Checks that the lengths fit into the length fields, `path` is the path used in the pattern
+/// matching the value.
+fn len_checks(path: TokenStream2, fields: &[Field], refutable: bool) -> TokenStream2 {
+    fields.iter().filter_map(|field| {
+        let target = field.len_of.as_ref()?;
+        let ty = &field.ty;
+        let pattern = quote!(#path { #target: field, .. });
+        let check = quote! {
+            if <#ty as ::core::convert::TryFrom<usize>>::try_from(field.len()).is_err() {
+                return ::core::result::Result::Err(::push_decode::error::LengthOverflow::new(field.len()));
+            }
+        };
+        Some(if refutable {
+            quote!(if let #pattern = value { #check })
+        } else {
+            quote!({ let #pattern = value; #check })
+        })
+    }).collect()
+}
+
+/// Returns the match arms computing the minimum number of bytes required in each state.
+///
+/// The current decoder may be complete without knowing it so the following fields have to be
+/// included.
+fn min_lens(state: &Ident, fields: &[Field]) -> TokenStream2 {
+    fields.iter().enumerate().map(|(i, field)| {
+        let step = &field.step;
+        let following = fields[(i + 1)..].iter().map(Field::min_required_bytes);
+        quote! {
+            #state::#step(decoder) => {
+                let min = ::push_decode::KnownMinLenDecoder::min_required_bytes(decoder);
+                #(let min = min.saturating_add(#following);)*
+                min
+            },
+        }
+    }).collect()
+}
+
+fn state_after(state: &Ident, fields: &[Field], index: usize) -> TokenStream2 {
+    match fields.get(index) {
+        Some(field) => {
+            let step = &field.step;
+            let decoder = field.decoder();
+            quote!(#state::#step(#decoder))
+        },
+        None => quote!(#state::Done),
+    }
+}
+
+fn encoder_state_after(state: &Ident, fields: &[Field], index: usize, accessor: impl Fn(&Field) -> TokenStream2) -> TokenStream2 {
+    match fields.get(index) {
+        Some(field) => {
+            let step = &field.step;
+            let encoder = match &field.len_of {
+                Some(member) => {
+                    let target = fields.iter()
+                        .find(|field| field.member == *member)
+                        .expect("the length field belongs to the same fields");
+                    field.len_encoder(accessor(target))
+                },
+                None => field.encoder(accessor(field)),
+            };
+            quote!(#state::#step(#encoder))
+        },
+        None => quote!(#state::Done(::core::marker::PhantomData)),
+    }
+}
@@ -0,0 +1,206 @@
+use push_decode::{Decode, Decoder, Encode, Encoder, KnownMinLenDecoder};
+
+#[derive(Debug, PartialEq, Decode, Encode)]
+struct Header {
+    version: u8,
+    #[push_decode(endian = "le")]
+    flags: u32,
+    magic: [u8; 2],
+    len: u16,
+    #[push_decode(len = "len")]
+    payload: Vec<u8>,
+    name_len: u8,
+    #[push_decode(len = "name_len")]
+    name: String,
+}
+
+#[derive(Debug, PartialEq, Decode, Encode)]
+struct Tuple(u16, #[push_decode(endian = "le")] u16);
+
+#[derive(Debug, PartialEq, Decode, Encode)]
+struct Unit;
+
+#[derive(Debug, PartialEq, Decode, Encode)]
+struct Nested {
+    tuple: Tuple,
+    unit: Unit,
+    tail: u8,
+}
+
+#[derive(Debug, PartialEq, Decode, Encode)]
+struct Name {
+    len: u32,
+    #[push_decode(len = "len")]
+    name: String,
+}
+
+#[derive(Debug, PartialEq, Decode, Encode)]
+enum Blob {
+    Empty,
+    Data(u8, #[push_decode(len = "0")] Vec<u8>),
+}
+
+#[derive(Debug, PartialEq, Decode, Encode)]
+#[push_decode(tag_type = "u16")]
+enum Message {
+    Ping { nonce: u64 },
+    #[push_decode(tag = 5)]
+    Pong(u64),
+    Stop,
+}
+
+fn encode(value: impl Encode) -> Vec<u8> {
+    let mut buf = Vec::new();
+    value.encoder().write_to_vec(&mut buf);
+    buf
+}
+
+#[track_caller]
+fn decode<T: Decode>(bytes: &[u8]) -> Result<T, <T::Decoder as Decoder>::Error> {
+    let mut decoder = T::Decoder::default();
+    assert_eq!(decoder.bytes_received(bytes)?, bytes.len());
+    decoder.end()
+}
+
+#[test]
+fn struct_roundtrip() {
+    let header = Header {
+        version: 1,
+        flags: 2,
+        magic: [3, 4],
+        len: 2,
+        payload: vec![5, 6],
+        name_len: 3,
+        name: "foo".to_owned(),
+    };
+    let mut bytes = Vec::new();
+    HeaderEncoder::new(&header).unwrap().write_to_vec(&mut bytes);
+    assert_eq!(bytes, [1, 2, 0, 0, 0, 3, 4, 0, 2, 5, 6, 3, b'f', b'o', b'o']);
+    assert_eq!(decode::<Header>(&bytes).unwrap(), header);
+}
+
+#[test]
+fn struct_split() {
+    let bytes = [1, 2, 0, 0, 0, 3, 4, 0, 2, 5, 6, 0, 42];
+    let mut decoder = HeaderDecoder::new();
+    for byte in &bytes[..12] {
+        assert_eq!(decoder.bytes_received(core::slice::from_ref(byte)).unwrap(), 1);
+    }
+    assert_eq!(decoder.bytes_received(&bytes[12..]).unwrap(), 0);
+    let header = decoder.end().unwrap();
+    assert_eq!(header.payload, [5, 6]);
+    assert_eq!(header.name, "");
+}
+
+#[test]
+fn struct_error_names_field() {
+    let error = decode::<Header>(&[1, 2, 0]).unwrap_err();
+    assert!(matches!(error, HeaderDecodeError::Flags(_)));
+    assert_eq!(error.to_string(), "failed to decode field `flags`");
+
+    let error = decode::<Header>(&[1, 2, 0, 0, 0, 3, 4, 0, 2, 5, 6, 1, 0xFF]).unwrap_err();
+    assert!(matches!(error, HeaderDecodeError::Name(_)));
+}
+
+#[test]
+fn huge_len() {
+    let mut decoder = NameDecoder::new();
+    assert_eq!(decoder.bytes_received(&[0xFF, 0xFF, 0xFF, 0xFF, b'a']).unwrap(), 5);
+    assert!(matches!(decoder.end().unwrap_err(), NameDecodeError::Name(push_decode::decoders::Utf8StringError::UnexpectedEnd(_))));
+}
+
+#[test]
+fn len_from_value() {
+    let name = Name { len: 2, name: "foo".to_owned() };
+    let mut bytes = Vec::new();
+    NameEncoder::new(&name).unwrap().write_to_vec(&mut bytes);
+    assert_eq!(bytes, [0, 0, 0, 3, b'f', b'o', b'o']);
+}
+
+#[test]
+fn variant_len_overflow() {
+    let mut bytes = Vec::new();
+    BlobEncoder::new(&Blob::Data(1, vec![42])).unwrap().write_to_vec(&mut bytes);
+    BlobEncoder::new(&Blob::Empty).unwrap().write_to_vec(&mut bytes);
+    assert_eq!(bytes, [1, 1, 42, 0]);
+    assert!(BlobEncoder::new(&Blob::Data(0, vec![0; 256])).is_err());
+}
+
+#[test]
+fn min_required_bytes() {
+    let bytes = [1, 2, 0, 0, 0, 3, 4, 0, 2, 5, 6, 0];
+    let mut decoder = HeaderDecoder::new();
+    for byte in &bytes {
+        assert_ne!(decoder.min_required_bytes(), 0);
+        decoder.bytes_received(core::slice::from_ref(byte)).unwrap();
+    }
+    assert_eq!(decoder.min_required_bytes(), 0);
+
+    let mut decoder = NestedDecoder::new();
+    assert_eq!(decoder.min_required_bytes(), 5);
+    decoder.bytes_received(&[0, 1, 2, 0]).unwrap();
+    assert_eq!(decoder.min_required_bytes(), 1);
+
+    let mut decoder = MessageDecoder::new();
+    decoder.bytes_received(&[0]).unwrap();
+    assert_eq!(decoder.min_required_bytes(), 1);
+    decoder.bytes_received(&[2]).unwrap();
+    assert_eq!(decoder.min_required_bytes(), 0);
+}
+
+#[test]
+fn error_source() {
+    use std::error::Error;
+
+    let error = decode::<Nested>(&[0, 1, 2]).unwrap_err();
+    let source = error.source().unwrap();
+    assert_eq!(source.to_string(), "failed to decode field `1`");
+    assert!(source.source().is_some());
+    assert!(decode::<Message>(&[0, 3]).unwrap_err().source().is_none());
+}
+
+#[test]
+fn tuple_struct() {
+    let value = Tuple(1, 1);
+    let bytes = encode(&value);
+    assert_eq!(bytes, [0, 1, 1, 0]);
+    assert_eq!(decode::<Tuple>(&bytes).unwrap(), value);
+    assert!(matches!(decode::<Tuple>(&[0, 1, 1]).unwrap_err(), TupleDecodeError::Field1(_)));
+}
+
+#[test]
+fn unit_struct() {
+    assert_eq!(encode(&Unit), []);
+    assert_eq!(decode::<Unit>(&[]).unwrap(), Unit);
+}
+
+#[test]
+fn nested() {
+    let value = Nested { tuple: Tuple(1, 2), unit: Unit, tail: 3 };
+    let bytes = encode(&value);
+    assert_eq!(bytes, [0, 1, 2, 0, 3]);
+    assert_eq!(decode::<Nested>(&bytes).unwrap(), value);
+    assert!(matches!(decode::<Nested>(&[0, 1, 2]).unwrap_err(), NestedDecodeError::Tuple(TupleDecodeError::Field1(_))));
+}
+
+#[test]
+fn enum_roundtrip() {
+    let cases = [
+        (Message::Ping { nonce: 1 }, vec![0, 0, 0, 0, 0, 0, 0, 0, 0, 1]),
+        (Message::Pong(2), vec![0, 5, 0, 0, 0, 0, 0, 0, 0, 2]),
+        (Message::Stop, vec![0, 2]),
+    ];
+    for (message, expected) in cases {
+        let bytes = encode(&message);
+        assert_eq!(bytes, expected);
+        assert_eq!(decode::<Message>(&bytes).unwrap(), message);
+    }
+}
+
+#[test]
+fn enum_errors() {
+    assert!(matches!(decode::<Message>(&[0, 3]).unwrap_err(), MessageDecodeError::UnknownTag(3)));
+    assert!(matches!(decode::<Message>(&[0]).unwrap_err(), MessageDecodeError::Tag(_)));
+    assert!(matches!(decode::<Message>(&[0, 0, 1]).unwrap_err(), MessageDecodeError::PingNonce(_)));
+    assert!(matches!(decode::<Message>(&[0, 5]).unwrap_err(), MessageDecodeError::Pong0(_)));
+}
@@ -0,0 +1,139 @@
+//! Tools for decoding and encoding values with bit granularity.
+//!
+//! Decoders and encoders in the rest of the crate are byte-oriented. Some formats (e.g. video
+//! codec headers or flag bitfields) pack multiple fields into bytes, so this module provides
+//! [`BitDecoder`] and [`BitEncoder`] traits that process one bit at a time. These are connected
+//! to the byte-oriented traits using [`BitReader`] and [`BitWriter`] adapters which also define
+//! the order of bits within a byte.
+//!
+//! The adapters always work with whole bytes: when the bit decoder finishes in the middle of a
+//! byte the remaining bits are skipped and when the bit encoder finishes in the middle of a byte
+//! the remaining bits are filled with padding.
+
+mod chain;
+mod exp_golomb;
+mod field;
+mod reader;
+mod writer;
+
+pub use chain::{Chain, EncoderChain};
+pub use exp_golomb::{ExpGolombDecoder, ExpGolombEncoder, SignedExpGolombDecoder};
+pub use exp_golomb::Error as ExpGolombError;
+pub use field::{BitFieldDecoder, BitFieldEncoder, FieldOverflow, FlagDecoder, FlagEncoder};
+pub use reader::BitReader;
+pub use writer::BitWriter;
+
+/// Decodes a value from a stream of bits.
+///
+/// This is the bit-oriented counterpart of [`Decoder`](crate::Decoder). The bits are pushed into
+/// the decoder one by one until [`min_required_bits`](Self::min_required_bits) returns 0.
+pub trait BitDecoder: Sized {
+    /// The type of the decoded value.
+    type Value;
+
+    /// Decoding error.
+    type Error;
+
+    /// Processes the next bit.
+    ///
+    /// This must not be called once [`min_required_bits`](Self::min_required_bits) returned 0.
+    fn decode_bit(&mut self, bit: bool) -> Result<(), Self::Error>;
+
+    /// Returns the minimum number of bits the decoder needs to finish.
+    ///
+    /// The returned value MUST be 0 if and only if the decoder doesn't need any more bits.
+    fn min_required_bits(&self) -> usize;
+
+    /// Finishes decoding and returns the value.
+    ///
+    /// Returns an error if the decoder didn't receive enough bits.
+    fn end(self) -> Result<Self::Value, Self::Error>;
+
+    /// Chains another decoder after this one to decode two values.
+    fn chain<D: BitDecoder>(self, following: D) -> Chain<Self, D> {
+        Chain::new(self, following)
+    }
+}
+
+/// Encodes a value as a stream of bits.
+///
+/// This is the bit-oriented counterpart of [`Encoder`](crate::Encoder).
+pub trait BitEncoder: Sized {
+    /// Returns the next bit or `None` if all bits were returned.
+    ///
+    /// Once `None` is returned the following calls MUST return `None` as well.
+    fn next_bit(&mut self) -> Option<bool>;
+
+    /// Chains another encoder after this one.
+    fn chain<E: BitEncoder>(self, following: E) -> EncoderChain<Self, E> {
+        EncoderChain::new(self, following)
+    }
+}
+
+/// The order of bits within a byte.
+///
+/// It also determines the significance of bits in [`BitFieldDecoder`] and [`BitFieldEncoder`]:
+/// the first bit of a field is the most significant one for [`MsbFirst`] and the least
+/// significant one for [`LsbFirst`].
+pub trait BitOrder: sealed::BitOrder {
+    #[doc(hidden)]
+    fn bit(byte: u8, index: u32) -> bool;
+    #[doc(hidden)]
+    fn set_bit(byte: &mut u8, index: u32);
+    #[doc(hidden)]
+    fn push_field_bit(field: u128, bit: bool, index: u32) -> u128;
+    #[doc(hidden)]
+    fn field_bit(field: u128, width: u32, index: u32) -> bool;
+}
+
+/// The most significant bit is first.
+#[derive(Debug, Clone, Copy)]
+pub struct MsbFirst {}
+
+/// The least significant bit is first.
+#[derive(Debug, Clone, Copy)]
+pub struct LsbFirst {}
+
+impl BitOrder for MsbFirst {
+    fn bit(byte: u8, index: u32) -> bool {
+        byte & (0x80 >> index) != 0
+    }
+
+    fn set_bit(byte: &mut u8, index: u32) {
+        *byte |= 0x80 >> index;
+    }
+
+    fn push_field_bit(field: u128, bit: bool, _index: u32) -> u128 {
+        field << 1 | u128::from(bit)
+    }
+
+    fn field_bit(field: u128, width: u32, index: u32) -> bool {
+        field >> (width - 1 - index) & 1 != 0
+    }
+}
+
+impl sealed::BitOrder for MsbFirst {}
+
+impl BitOrder for LsbFirst {
+    fn bit(byte: u8, index: u32) -> bool {
+        byte & (1 << index) != 0
+    }
+
+    fn set_bit(byte: &mut u8, index: u32) {
+        *byte |= 1 << index;
+    }
+
+    fn push_field_bit(field: u128, bit: bool, index: u32) -> u128 {
+        field | u128::from(bit) << index
+    }
+
+    fn field_bit(field: u128, _width: u32, index: u32) -> bool {
+        field >> index & 1 != 0
+    }
+}
+
+impl sealed::BitOrder for LsbFirst {}
+
+mod sealed {
+    pub trait BitOrder {}
+}
@@ -0,0 +1,110 @@
+use core::fmt;
+use either::Either;
+use super::{BitDecoder, BitEncoder};
+
+/// Decodes two values one after another.
+///
+/// Returned from [`BitDecoder::chain`].
+pub struct Chain<First: BitDecoder, Second: BitDecoder>(State<First, Second>);
+
+impl<First: BitDecoder, Second: BitDecoder> Chain<First, Second> {
+    pub(crate) fn new(first: First, second: Second) -> Self {
+        Chain(State::First(first, second))
+    }
+}
+
+impl<First: BitDecoder + Default, Second: BitDecoder + Default> Default for Chain<First, Second> {
+    fn default() -> Self {
+        Chain::new(Default::default(), Default::default())
+    }
+}
+
+impl <First, Second> fmt::Debug for Chain<First, Second>
+where First: BitDecoder + fmt::Debug,
+      Second: BitDecoder + fmt::Debug,
+      First::Value: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.0 {
+            State::First(a, b) => f.debug_tuple("Chain::First").field(a).field(b).finish(),
+            State::Second(a, b) => f.debug_tuple("Chain::Second").field(a).field(b).finish(),
+            State::Errored => f.debug_tuple("Chain::Errored").finish(),
+            State::Panicked => f.debug_tuple("Chain::Panicked").finish(),
+        }
+    }
+}
+
+enum State<A: BitDecoder, B: BitDecoder> {
+    First(A, B),
+    Second(A::Value, B),
+    Errored,
+    Panicked
+}
+
+impl<First: BitDecoder, Second: BitDecoder> BitDecoder for Chain<First, Second> {
+    type Value = (First::Value, Second::Value);
+    type Error = Either<First::Error, Second::Error>;
+
+    fn decode_bit(&mut self, bit: bool) -> Result<(), Self::Error> {
+        if let State::First(first, _) = &self.0 {
+            if first.min_required_bits() == 0 {
+                let (first, second) = match core::mem::replace(&mut self.0, State::Panicked) {
+                    State::First(first, second) => (first, second),
+                    _ => unreachable!(),
+                };
+                let first_val = first.end()
+                    .map_err(|error| { self.0 = State::Errored; Either::Left(error)})?;
+                self.0 = State::Second(first_val, second);
+            }
+        }
+        match &mut self.0 {
+            State::First(first, _) => first.decode_bit(bit).map_err(Either::Left),
+            State::Second(_, second) => second.decode_bit(bit).map_err(Either::Right),
+            State::Errored => panic!("use of failed decoder"),
+            State::Panicked => panic!("use of panicked decoder"),
+        }
+    }
+
+    fn min_required_bits(&self) -> usize {
+        match &self.0 {
+            State::First(first, second) => first.min_required_bits() + second.min_required_bits(),
+            State::Second(_, second) => second.min_required_bits(),
+            State::Errored => panic!("use of failed decoder"),
+            State::Panicked => panic!("use of panicked decoder"),
+        }
+    }
+
+    fn end(self) -> Result<Self::Value, Self::Error> {
+        match self.0 {
+            State::First(first, second) => {
+                let first = first.end().map_err(Either::Left)?;
+                let second = second.end().map_err(Either::Right)?;
+                Ok((first, second))
+            },
+            State::Second(first, second) => Ok((first, second.end().map_err(Either::Right)?)),
+            State::Errored => panic!("use of failed decoder"),
+            State::Panicked => panic!("use of panicked decoder"),
+        }
+    }
+}
+
+/// Encodes two values one after another.
+///
+/// Returned from [`BitEncoder::chain`].
+#[derive(Debug, Clone)]
+pub struct EncoderChain<First, Second>(First, Second);
+
+impl<First: BitEncoder, Second: BitEncoder> EncoderChain<First, Second> {
+    pub(crate) fn new(first: First, second: Second) -> Self {
+        EncoderChain(first, second)
+    }
+}
+
+impl<First: BitEncoder, Second: BitEncoder> BitEncoder for EncoderChain<First, Second> {
+    fn next_bit(&mut self) -> Option<bool> {
+        match self.0.next_bit() {
+            Some(bit) => Some(bit),
+            None => self.1.next_bit(),
+        }
+    }
+}
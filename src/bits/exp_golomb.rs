@@ -0,0 +1,340 @@
+use core::fmt;
+use core::marker::PhantomData;
+use crate::error::UnexpectedEnd;
+use crate::int::{Signed, Unsigned};
+use super::{BitDecoder, BitEncoder};
+
+/// Exp-Golomb code split into the number of leading zeros and the bits following the marker bit.
+///
+/// The encoded number is `2^prefix - 1 + suffix`.
+#[derive(Debug, Default)]
+struct Code {
+    prefix: u32,
+    suffix: u128,
+    suffix_received: Option<u32>,
+}
+
+impl Code {
+    // The code of `u128::MAX` has 128 leading zeros so more would overflow.
+    const MAX_PREFIX: u32 = 128;
+
+    fn decode_bit(&mut self, bit: bool) -> Result<(), Error> {
+        match &mut self.suffix_received {
+            None if bit => self.suffix_received = Some(0),
+            None if self.prefix == Self::MAX_PREFIX => return Err(Error::Overflow),
+            None => self.prefix += 1,
+            Some(received) => {
+                self.suffix = self.suffix << 1 | u128::from(bit);
+                *received += 1;
+            },
+        }
+        Ok(())
+    }
+
+    fn min_required_bits(&self) -> usize {
+        match self.suffix_received {
+            None => self.prefix as usize + 1,
+            Some(received) => (self.prefix - received) as usize,
+        }
+    }
+
+    fn end(self) -> Result<(u32, u128), Error> {
+        match self.min_required_bits() {
+            0 => Ok((self.prefix, self.suffix)),
+            missing => Err(Error::UnexpectedEnd(UnexpectedEnd { missing: (missing + 7) / 8 })),
+        }
+    }
+}
+
+/// Returns `2^prefix - 1`.
+fn code_base(prefix: u32) -> u128 {
+    u128::MAX.checked_shr(128 - prefix).unwrap_or(0)
+}
+
+fn split_code(value: u128) -> (u32, u128) {
+    // `prefix` is the largest number such that `2^prefix - 1 <= value`
+    let prefix = match value.checked_add(1) {
+        Some(value) => 127 - value.leading_zeros(),
+        None => 128,
+    };
+    (prefix, value - code_base(prefix))
+}
+
+/// Decodes an unsigned Exp-Golomb-coded integer.
+///
+/// This is the `ue(v)` syntax element of H.264 and similar formats. The bits of the code are
+/// always most significant first.
+pub struct ExpGolombDecoder<T: Unsigned>(Code, PhantomData<fn() -> T>);
+
+impl<T: Unsigned> ExpGolombDecoder<T> {
+    /// Creates the decoder.
+    pub fn new() -> Self {
+        ExpGolombDecoder(Code::default(), PhantomData)
+    }
+}
+
+impl<T: Unsigned> Default for ExpGolombDecoder<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Unsigned> fmt::Debug for ExpGolombDecoder<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("ExpGolombDecoder").field(&self.0).finish()
+    }
+}
+
+impl<T: Unsigned> BitDecoder for ExpGolombDecoder<T> {
+    type Value = T;
+    type Error = Error;
+
+    fn decode_bit(&mut self, bit: bool) -> Result<(), Self::Error> {
+        self.0.decode_bit(bit)
+    }
+
+    fn min_required_bits(&self) -> usize {
+        self.0.min_required_bits()
+    }
+
+    fn end(self) -> Result<Self::Value, Self::Error> {
+        let (prefix, suffix) = self.0.end()?;
+        let value = code_base(prefix).checked_add(suffix).ok_or(Error::Overflow)?;
+        if T::BITS < 128 && value >> T::BITS != 0 {
+            return Err(Error::Overflow);
+        }
+        Ok(T::from_u128(value))
+    }
+}
+
+/// Decodes a signed Exp-Golomb-coded integer.
+///
+/// This is the `se(v)` syntax element of H.264 and similar formats: the code `k` represents
+/// `(k + 1) / 2` if `k` is odd and `-k / 2` if it's even.
+pub struct SignedExpGolombDecoder<T: Signed>(Code, PhantomData<fn() -> T>);
+
+impl<T: Signed> SignedExpGolombDecoder<T> {
+    /// Creates the decoder.
+    pub fn new() -> Self {
+        SignedExpGolombDecoder(Code::default(), PhantomData)
+    }
+}
+
+impl<T: Signed> Default for SignedExpGolombDecoder<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Signed> fmt::Debug for SignedExpGolombDecoder<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("SignedExpGolombDecoder").field(&self.0).finish()
+    }
+}
+
+impl<T: Signed> BitDecoder for SignedExpGolombDecoder<T> {
+    type Value = T;
+    type Error = Error;
+
+    fn decode_bit(&mut self, bit: bool) -> Result<(), Self::Error> {
+        self.0.decode_bit(bit)
+    }
+
+    fn min_required_bits(&self) -> usize {
+        self.0.min_required_bits()
+    }
+
+    fn end(self) -> Result<Self::Value, Self::Error> {
+        let (prefix, suffix) = self.0.end()?;
+        if prefix == 0 {
+            return Ok(T::from_i128(0));
+        }
+        // `(k + 1) / 2` where `k + 1 = 2^prefix + suffix` computed without overflowing
+        let magnitude = (1u128 << (prefix - 1)) + (suffix >> 1);
+        let value = if suffix & 1 == 0 {
+            if magnitude > i128::MAX as u128 {
+                return Err(Error::Overflow);
+            }
+            magnitude as i128
+        } else {
+            if magnitude > i128::MIN.unsigned_abs() {
+                return Err(Error::Overflow);
+            }
+            // wrapping makes `i128::MIN` correct
+            (magnitude as i128).wrapping_neg()
+        };
+        // the value fits if all bits above the sign bit are same as the sign bit
+        if T::BITS < 128 && (value >> (T::BITS - 1) != 0 && value >> (T::BITS - 1) != -1) {
+            return Err(Error::Overflow);
+        }
+        Ok(T::from_i128(value))
+    }
+}
+
+/// Encodes an integer using Exp-Golomb code.
+///
+/// The bits of the code are always most significant first.
+#[derive(Debug, Clone)]
+pub struct ExpGolombEncoder {
+    prefix: u32,
+    suffix: u128,
+    pos: u32,
+}
+
+impl ExpGolombEncoder {
+    /// Creates the encoder for an unsigned integer (`ue(v)`).
+    pub fn new<T: Unsigned>(value: T) -> Self {
+        let (prefix, suffix) = split_code(value.into_u128());
+        Self::from_code(prefix, suffix)
+    }
+
+    /// Creates the encoder for a signed integer (`se(v)`).
+    ///
+    /// This is the counterpart of [`SignedExpGolombDecoder`].
+    pub fn new_signed<T: Signed>(value: T) -> Self {
+        let value = value.into_i128();
+        if value > 0 {
+            let (prefix, suffix) = split_code(value as u128 * 2 - 1);
+            Self::from_code(prefix, suffix)
+        } else if value == i128::MIN {
+            // the code is 2^128 which doesn't fit into u128
+            Self::from_code(128, 1)
+        } else {
+            let (prefix, suffix) = split_code(value.unsigned_abs() * 2);
+            Self::from_code(prefix, suffix)
+        }
+    }
+
+    fn from_code(prefix: u32, suffix: u128) -> Self {
+        ExpGolombEncoder {
+            prefix,
+            suffix,
+            pos: 0,
+        }
+    }
+}
+
+impl BitEncoder for ExpGolombEncoder {
+    fn next_bit(&mut self) -> Option<bool> {
+        let len = self.prefix * 2 + 1;
+        let bit = if self.pos < self.prefix {
+            false
+        } else if self.pos == self.prefix {
+            true
+        } else if self.pos < len {
+            self.suffix >> (len - 1 - self.pos) & 1 != 0
+        } else {
+            return None;
+        };
+        self.pos += 1;
+        Some(bit)
+    }
+}
+
+/// Error returned when decoding Exp-Golomb-coded integer fails.
+#[derive(Debug, Clone)]
+pub enum Error {
+    /// The stream ended before the whole code was received.
+    UnexpectedEnd(UnexpectedEnd),
+    /// The encoded value doesn't fit into the target type.
+    Overflow,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::UnexpectedEnd(_) => write!(f, "unexpected end"),
+            Error::Overflow => write!(f, "the encoded integer is out of range"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::UnexpectedEnd(error) => Some(error),
+            Error::Overflow => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Error, ExpGolombDecoder, ExpGolombEncoder, SignedExpGolombDecoder};
+    use crate::bits::{BitDecoder, BitEncoder};
+
+    fn roundtrip<D: BitDecoder>(mut encoder: ExpGolombEncoder, mut decoder: D) -> Result<D::Value, D::Error> {
+        while let Some(bit) = encoder.next_bit() {
+            decoder.decode_bit(bit)?;
+        }
+        decoder.end()
+    }
+
+    #[test]
+    fn known_codes() {
+        // 0 -> 1, 1 -> 010, 2 -> 011, 3 -> 00100
+        let cases: [(u8, &[bool]); 4] = [
+            (0, &[true]),
+            (1, &[false, true, false]),
+            (2, &[false, true, true]),
+            (3, &[false, false, true, false, false]),
+        ];
+        for (value, bits) in cases {
+            let mut encoder = ExpGolombEncoder::new(value);
+            for bit in bits {
+                assert_eq!(encoder.next_bit(), Some(*bit));
+            }
+            assert_eq!(encoder.next_bit(), None);
+
+            let mut decoder = ExpGolombDecoder::<u8>::new();
+            for bit in bits {
+                decoder.decode_bit(*bit).unwrap();
+            }
+            assert_eq!(decoder.min_required_bits(), 0);
+            assert_eq!(decoder.end().unwrap(), value);
+        }
+    }
+
+    #[test]
+    fn unsigned_roundtrip() {
+        for value in [0, 1, 42, u64::MAX as u128, u128::MAX - 1, u128::MAX] {
+            assert_eq!(roundtrip(ExpGolombEncoder::new(value), ExpGolombDecoder::<u128>::new()).unwrap(), value);
+        }
+    }
+
+    #[test]
+    fn signed_roundtrip() {
+        for value in [0, 1, -1, 2, -2, 1000, i128::MAX, i128::MIN] {
+            assert_eq!(roundtrip(ExpGolombEncoder::new_signed(value), SignedExpGolombDecoder::<i128>::new()).unwrap(), value);
+        }
+        // 1 -> 010, -1 -> 011
+        let mut encoder = ExpGolombEncoder::new_signed(-1i8);
+        assert_eq!(encoder.next_bit(), Some(false));
+        assert_eq!(encoder.next_bit(), Some(true));
+        assert_eq!(encoder.next_bit(), Some(true));
+    }
+
+    #[test]
+    fn overflow() {
+        let error = roundtrip(ExpGolombEncoder::new(256u16), ExpGolombDecoder::<u8>::new()).unwrap_err();
+        assert!(matches!(error, Error::Overflow));
+        let error = roundtrip(ExpGolombEncoder::new_signed(-129i16), SignedExpGolombDecoder::<i8>::new()).unwrap_err();
+        assert!(matches!(error, Error::Overflow));
+
+        let mut decoder = ExpGolombDecoder::<u128>::new();
+        for _ in 0..128 {
+            decoder.decode_bit(false).unwrap();
+        }
+        assert!(matches!(decoder.decode_bit(false), Err(Error::Overflow)));
+    }
+
+    #[test]
+    fn truncated() {
+        let mut decoder = ExpGolombDecoder::<u32>::new();
+        decoder.decode_bit(false).unwrap();
+        decoder.decode_bit(true).unwrap();
+        assert_eq!(decoder.min_required_bits(), 1);
+        assert!(matches!(decoder.end(), Err(Error::UnexpectedEnd(_))));
+    }
+}
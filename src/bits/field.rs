@@ -0,0 +1,244 @@
+use core::fmt;
+use core::marker::PhantomData;
+use crate::error::UnexpectedEnd;
+use crate::int::Unsigned;
+use super::{BitDecoder, BitEncoder, BitOrder, MsbFirst};
+
+fn missing_bytes(missing_bits: u32) -> usize {
+    ((missing_bits + 7) / 8) as usize
+}
+
+/// Decodes an unsigned integer stored in a fixed number of bits.
+///
+/// The significance of bits is determined by the bit order `O`.
+#[derive(Debug)]
+pub struct BitFieldDecoder<T: Unsigned, O: BitOrder = MsbFirst> {
+    value: u128,
+    width: u32,
+    received: u32,
+    _phantom: PhantomData<(fn() -> T, O)>,
+}
+
+impl<T: Unsigned, O: BitOrder> BitFieldDecoder<T, O> {
+    /// Creates the decoder for a field `width` bits wide.
+    ///
+    /// # Panics
+    ///
+    /// If `width` is larger than the number of bits of `T`.
+    pub fn new(width: u32) -> Self {
+        assert!(width <= T::BITS, "{} bits don't fit into {}", width, core::any::type_name::<T>());
+        BitFieldDecoder {
+            value: 0,
+            width,
+            received: 0,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T: Unsigned, O: BitOrder> BitDecoder for BitFieldDecoder<T, O> {
+    type Value = T;
+    type Error = UnexpectedEnd;
+
+    fn decode_bit(&mut self, bit: bool) -> Result<(), Self::Error> {
+        self.value = O::push_field_bit(self.value, bit, self.received);
+        self.received += 1;
+        Ok(())
+    }
+
+    fn min_required_bits(&self) -> usize {
+        (self.width - self.received) as usize
+    }
+
+    fn end(self) -> Result<Self::Value, Self::Error> {
+        if self.received < self.width {
+            Err(UnexpectedEnd { missing: missing_bytes(self.width - self.received) })
+        } else {
+            // fits because `new` checks the width
+            Ok(T::from_u128(self.value))
+        }
+    }
+}
+
+/// Decodes a single bit as `bool`.
+#[derive(Debug, Default)]
+pub struct FlagDecoder(Option<bool>);
+
+impl FlagDecoder {
+    /// Creates the decoder.
+    pub fn new() -> Self {
+        FlagDecoder(None)
+    }
+}
+
+impl BitDecoder for FlagDecoder {
+    type Value = bool;
+    type Error = UnexpectedEnd;
+
+    fn decode_bit(&mut self, bit: bool) -> Result<(), Self::Error> {
+        self.0 = Some(bit);
+        Ok(())
+    }
+
+    fn min_required_bits(&self) -> usize {
+        match self.0 {
+            None => 1,
+            Some(_) => 0,
+        }
+    }
+
+    fn end(self) -> Result<Self::Value, Self::Error> {
+        self.0.ok_or(UnexpectedEnd { missing: 1 })
+    }
+}
+
+/// Encodes an unsigned integer into a fixed number of bits.
+///
+/// The significance of bits is determined by the bit order `O`.
+#[derive(Debug, Clone)]
+pub struct BitFieldEncoder<O: BitOrder = MsbFirst> {
+    value: u128,
+    width: u32,
+    pos: u32,
+    _phantom: PhantomData<O>,
+}
+
+impl<O: BitOrder> BitFieldEncoder<O> {
+    /// Creates the encoder for a field `width` bits wide.
+    ///
+    /// Returns an error if the value doesn't fit into `width` bits. The field may be wider than
+    /// `T` in which case the value is padded with zeros.
+    ///
+    /// # Panics
+    ///
+    /// If `width` is larger than 128.
+    pub fn new<T: Unsigned>(value: T, width: u32) -> Result<Self, FieldOverflow> {
+        assert!(width <= 128, "{} bits is more than the maximum of 128", width);
+        let value = value.into_u128();
+        if width < 128 && value >> width != 0 {
+            return Err(FieldOverflow { value, width });
+        }
+        Ok(BitFieldEncoder {
+            value,
+            width,
+            pos: 0,
+            _phantom: PhantomData,
+        })
+    }
+}
+
+impl<O: BitOrder> BitEncoder for BitFieldEncoder<O> {
+    fn next_bit(&mut self) -> Option<bool> {
+        if self.pos < self.width {
+            let bit = O::field_bit(self.value, self.width, self.pos);
+            self.pos += 1;
+            Some(bit)
+        } else {
+            None
+        }
+    }
+}
+
+/// Encodes `bool` as a single bit.
+#[derive(Debug, Clone)]
+pub struct FlagEncoder(Option<bool>);
+
+impl FlagEncoder {
+    /// Creates the encoder of `value`.
+    pub fn new(value: bool) -> Self {
+        FlagEncoder(Some(value))
+    }
+}
+
+impl BitEncoder for FlagEncoder {
+    fn next_bit(&mut self) -> Option<bool> {
+        self.0.take()
+    }
+}
+
+/// Error returned when a value doesn't fit into the width of a bit field.
+#[derive(Debug, Clone)]
+pub struct FieldOverflow {
+    value: u128,
+    width: u32,
+}
+
+impl fmt::Display for FieldOverflow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "the value {} doesn't fit into {} bits", self.value, self.width)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for FieldOverflow {}
+
+#[cfg(test)]
+mod tests {
+    use super::{BitFieldDecoder, BitFieldEncoder};
+    use crate::bits::{BitDecoder, BitEncoder, LsbFirst, MsbFirst};
+
+    fn decode<D: BitDecoder>(mut decoder: D, bits: &[bool]) -> Result<D::Value, D::Error> {
+        for bit in bits {
+            decoder.decode_bit(*bit)?;
+        }
+        decoder.end()
+    }
+
+    #[test]
+    fn msb_first() {
+        let decoder = BitFieldDecoder::<u8, MsbFirst>::new(3);
+        assert_eq!(decode(decoder, &[true, false, false]).unwrap(), 4);
+    }
+
+    #[test]
+    fn lsb_first() {
+        let decoder = BitFieldDecoder::<u8, LsbFirst>::new(3);
+        assert_eq!(decode(decoder, &[true, false, false]).unwrap(), 1);
+    }
+
+    #[test]
+    fn truncated() {
+        let decoder = BitFieldDecoder::<u16, MsbFirst>::new(12);
+        assert_eq!(decode(decoder, &[true]).unwrap_err().missing, 2);
+    }
+
+    #[test]
+    fn zero_width() {
+        let decoder = BitFieldDecoder::<u8, MsbFirst>::new(0);
+        assert_eq!(decoder.min_required_bits(), 0);
+        assert_eq!(decoder.end().unwrap(), 0);
+    }
+
+    #[test]
+    fn encode() {
+        let mut encoder = BitFieldEncoder::<MsbFirst>::new(4u8, 3).unwrap();
+        assert_eq!(encoder.next_bit(), Some(true));
+        assert_eq!(encoder.next_bit(), Some(false));
+        assert_eq!(encoder.next_bit(), Some(false));
+        assert_eq!(encoder.next_bit(), None);
+        assert_eq!(encoder.next_bit(), None);
+
+        let mut encoder = BitFieldEncoder::<LsbFirst>::new(1u8, 2).unwrap();
+        assert_eq!(encoder.next_bit(), Some(true));
+        assert_eq!(encoder.next_bit(), Some(false));
+        assert_eq!(encoder.next_bit(), None);
+    }
+
+    #[test]
+    fn overflow() {
+        assert!(BitFieldEncoder::<MsbFirst>::new(8u8, 3).is_err());
+        assert!(BitFieldEncoder::<MsbFirst>::new(u128::MAX, 128).is_ok());
+    }
+
+    #[test]
+    #[should_panic(expected = "129 bits is more than the maximum of 128")]
+    fn encoder_too_wide() {
+        let _ = BitFieldEncoder::<MsbFirst>::new(0u128, 129);
+    }
+
+    #[test]
+    fn encoder_wider_than_value() {
+        assert!(BitFieldEncoder::<MsbFirst>::new(1u8, 16).is_ok());
+        assert!(BitFieldEncoder::<MsbFirst>::new(u128::MAX, 128).is_ok());
+    }
+}
@@ -0,0 +1,123 @@
+use core::fmt;
+use core::marker::PhantomData;
+use crate::{Decoder, KnownMinLenDecoder};
+use super::{BitDecoder, BitOrder, MsbFirst};
+
+/// Adapts [`BitDecoder`] to [`Decoder`].
+///
+/// The bits are taken from each byte in order `O`. The byte containing the last bit of the value
+/// is consumed entirely, its remaining bits are skipped as padding.
+pub struct BitReader<D: BitDecoder, O: BitOrder = MsbFirst> {
+    decoder: D,
+    byte: u8,
+    // 8 means there are no buffered bits
+    pos: u32,
+    _phantom: PhantomData<O>,
+}
+
+impl<D: BitDecoder, O: BitOrder> BitReader<D, O> {
+    /// Creates the reader using the given bit decoder.
+    pub fn new(decoder: D) -> Self {
+        BitReader {
+            decoder,
+            byte: 0,
+            pos: 8,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<D: BitDecoder + Default, O: BitOrder> Default for BitReader<D, O> {
+    fn default() -> Self {
+        Self::new(D::default())
+    }
+}
+
+impl<D: BitDecoder + fmt::Debug, O: BitOrder> fmt::Debug for BitReader<D, O> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BitReader")
+            .field("decoder", &self.decoder)
+            .field("byte", &self.byte)
+            .field("pos", &self.pos)
+            .finish()
+    }
+}
+
+impl<D: BitDecoder, O: BitOrder> Decoder for BitReader<D, O> {
+    type Value = D::Value;
+    type Error = D::Error;
+
+    fn decode_chunk(&mut self, bytes: &mut &[u8]) -> Result<(), Self::Error> {
+        while self.decoder.min_required_bits() > 0 {
+            if self.pos == 8 {
+                match bytes.split_first() {
+                    Some((byte, rest)) => {
+                        self.byte = *byte;
+                        self.pos = 0;
+                        *bytes = rest;
+                    },
+                    None => break,
+                }
+            }
+            self.decoder.decode_bit(O::bit(self.byte, self.pos))?;
+            self.pos += 1;
+        }
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Value, Self::Error> {
+        self.decoder.end()
+    }
+}
+
+impl<D: BitDecoder, O: BitOrder> KnownMinLenDecoder for BitReader<D, O> {
+    fn min_required_bytes(&self) -> usize {
+        // the buffered bits are always processed before returning from `decode_chunk`
+        (self.decoder.min_required_bits() + 7) / 8
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BitReader;
+    use crate::{Decoder, KnownMinLenDecoder};
+    use crate::bits::{BitDecoder, BitFieldDecoder, ExpGolombDecoder, FlagDecoder, LsbFirst, MsbFirst};
+
+    #[test]
+    fn msb_first_fields() {
+        let fields = BitFieldDecoder::<u8, MsbFirst>::new(3)
+            .chain(FlagDecoder::new())
+            .chain(BitFieldDecoder::<u16, MsbFirst>::new(10));
+        let mut decoder = BitReader::<_, MsbFirst>::new(fields);
+        assert_eq!(decoder.min_required_bytes(), 2);
+        assert_eq!(decoder.bytes_received(&[0b1011_0000]).unwrap(), 1);
+        assert_eq!(decoder.min_required_bytes(), 1);
+        assert_eq!(decoder.bytes_received(&[0b0111_1100, 42]).unwrap(), 1);
+        assert_eq!(decoder.min_required_bytes(), 0);
+        assert_eq!(decoder.end().unwrap(), ((5, true), 0b00_0001_1111));
+    }
+
+    #[test]
+    fn lsb_first_fields() {
+        let fields = BitFieldDecoder::<u8, LsbFirst>::new(3)
+            .chain(BitFieldDecoder::<u8, LsbFirst>::new(5));
+        let mut decoder = BitReader::<_, LsbFirst>::new(fields);
+        assert_eq!(decoder.bytes_received(&[0b1010_1101]).unwrap(), 1);
+        assert_eq!(decoder.end().unwrap(), (0b101, 0b10101));
+    }
+
+    #[test]
+    fn exp_golomb_padding() {
+        let mut decoder = BitReader::<_, MsbFirst>::new(ExpGolombDecoder::<u32>::new());
+        assert_eq!(decoder.bytes_received(&[0b0010_0111, 0]).unwrap(), 1);
+        assert_eq!(decoder.end().unwrap(), 3);
+    }
+
+    #[test]
+    fn truncated() {
+        let mut decoder = BitReader::<_, MsbFirst>::new(BitFieldDecoder::<u16, MsbFirst>::new(12));
+        assert_eq!(decoder.bytes_received(&[0xFF]).unwrap(), 1);
+        assert_eq!(decoder.min_required_bytes(), 1);
+        decoder.end().unwrap_err();
+    }
+}
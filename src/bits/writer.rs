@@ -0,0 +1,136 @@
+use core::marker::PhantomData;
+use crate::Encoder;
+use super::{BitEncoder, BitOrder, MsbFirst};
+
+const BUF_SIZE: usize = 32;
+
+/// Adapts [`BitEncoder`] to [`Encoder`].
+///
+/// The bits are stored into each byte in order `O`. If the number of bits is not a multiple of
+/// eight the last byte is filled with padding bits which are zeros by default.
+#[must_use = "encoders do nothing by themselves"]
+#[derive(Debug, Clone)]
+pub struct BitWriter<E: BitEncoder, O: BitOrder = MsbFirst> {
+    encoder: E,
+    buf: [u8; BUF_SIZE],
+    len: usize,
+    padding: bool,
+    _phantom: PhantomData<O>,
+}
+
+impl<E: BitEncoder, O: BitOrder> BitWriter<E, O> {
+    /// Creates the writer padding with zeros.
+    pub fn new(encoder: E) -> Self {
+        Self::with_padding(encoder, false)
+    }
+
+    /// Creates the writer padding with the given bit.
+    pub fn with_padding(encoder: E, padding: bool) -> Self {
+        let mut writer = BitWriter {
+            encoder,
+            buf: [0; BUF_SIZE],
+            len: 0,
+            padding,
+            _phantom: PhantomData,
+        };
+        writer.fill();
+        writer
+    }
+
+    fn fill(&mut self) {
+        self.len = 0;
+        while self.len < BUF_SIZE {
+            let mut byte = 0;
+            let mut bits = 0;
+            while bits < 8 {
+                match self.encoder.next_bit() {
+                    Some(bit) => {
+                        if bit {
+                            O::set_bit(&mut byte, bits);
+                        }
+                        bits += 1;
+                    },
+                    None => break,
+                }
+            }
+            if bits == 0 {
+                break;
+            }
+            if self.padding {
+                for index in bits..8 {
+                    O::set_bit(&mut byte, index);
+                }
+            }
+            self.buf[self.len] = byte;
+            self.len += 1;
+            if bits < 8 {
+                break;
+            }
+        }
+    }
+}
+
+impl<E: BitEncoder, O: BitOrder> Encoder for BitWriter<E, O> {
+    fn encoded_chunk(&self) -> &[u8] {
+        &self.buf[..self.len]
+    }
+
+    fn next(&mut self) -> bool {
+        self.fill();
+        self.len > 0
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg(test)]
+mod tests {
+    use super::BitWriter;
+    use crate::Decoder;
+    use crate::test_utils::encode;
+    use crate::bits::{BitDecoder, BitEncoder, BitFieldDecoder, BitFieldEncoder, BitReader, ExpGolombDecoder, ExpGolombEncoder, FlagEncoder, LsbFirst, MsbFirst};
+
+    #[test]
+    fn msb_first_padding() {
+        let fields = BitFieldEncoder::<MsbFirst>::new(5u8, 3).unwrap()
+            .chain(FlagEncoder::new(true))
+            .chain(BitFieldEncoder::<MsbFirst>::new(0b1111111111u16, 10).unwrap());
+        assert_eq!(encode(BitWriter::<_, MsbFirst>::new(fields.clone())), [0b1011_1111, 0b1111_1100]);
+        assert_eq!(encode(BitWriter::<_, MsbFirst>::with_padding(fields, true)), [0b1011_1111, 0b1111_1111]);
+    }
+
+    #[test]
+    fn lsb_first() {
+        let fields = BitFieldEncoder::<LsbFirst>::new(0b101u8, 3).unwrap()
+            .chain(BitFieldEncoder::<LsbFirst>::new(0b10101u8, 5).unwrap());
+        assert_eq!(encode(BitWriter::<_, LsbFirst>::new(fields)), [0b1010_1101]);
+    }
+
+    #[test]
+    fn empty() {
+        let encoder = BitWriter::<_, MsbFirst>::new(FlagEncoder::new(true).chain(BitFieldEncoder::<MsbFirst>::new(0u8, 0).unwrap()));
+        assert_eq!(encode(encoder), [0x80]);
+        let encoder = BitWriter::<_, MsbFirst>::new(BitFieldEncoder::<MsbFirst>::new(0u8, 0).unwrap());
+        assert_eq!(encode(encoder), []);
+    }
+
+    #[test]
+    fn multiple_chunks() {
+        let encoder = BitFieldEncoder::<MsbFirst>::new(u128::MAX, 128).unwrap()
+            .chain(BitFieldEncoder::<MsbFirst>::new(u128::MAX, 128).unwrap())
+            .chain(FlagEncoder::new(true));
+        let bytes = encode(BitWriter::<_, MsbFirst>::new(encoder));
+        assert_eq!(bytes.len(), 33);
+        assert_eq!(bytes[32], 0x80);
+    }
+
+    #[test]
+    fn roundtrip() {
+        for value in [0u64, 1, 7, 1000, u64::MAX] {
+            let encoder = ExpGolombEncoder::new(value).chain(BitFieldEncoder::<MsbFirst>::new(3u8, 2).unwrap());
+            let bytes = encode(BitWriter::<_, MsbFirst>::new(encoder));
+            let mut decoder = BitReader::<_, MsbFirst>::new(ExpGolombDecoder::<u64>::new().chain(BitFieldDecoder::<u8, MsbFirst>::new(2)));
+            assert_eq!(decoder.bytes_received(&bytes).unwrap(), bytes.len());
+            assert_eq!(decoder.end().unwrap(), (value, 3));
+        }
+    }
+}
@@ -0,0 +1,28 @@
+//! Checksums of encoded data.
+//!
+//! This module defines the [`Checksum`] trait which can be implemented for any checksum or hash
+//! algorithm (e.g. CRC32, Adler-32 or truncated double-SHA256) to be used with
+//! [`Decoder::verify_checksum`](crate::Decoder::verify_checksum) and
+//! [`Encoder::append_checksum`](crate::Encoder::append_checksum).
+
+/// Incrementally computed checksum of a byte stream.
+///
+/// This is similar to [`core::hash::Hasher`] but the produced digest is a sequence of bytes
+/// exactly as it's encoded after the data.
+pub trait Checksum {
+    /// The bytes of the computed checksum.
+    ///
+    /// This is usually an array.
+    type Digest: AsRef<[u8]> + AsMut<[u8]> + Clone;
+
+    /// The length of the digest in bytes.
+    ///
+    /// This MUST be equal to the length of the slice returned from the digest.
+    const LEN: usize;
+
+    /// Processes the bytes.
+    fn update(&mut self, bytes: &[u8]);
+
+    /// Returns the checksum of all processed bytes.
+    fn finalize(self) -> Self::Digest;
+}
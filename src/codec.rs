@@ -0,0 +1,144 @@
+//! Integration with [`tokio_util::codec`](actual_tokio_util::codec).
+
+use core::marker::PhantomData;
+use bytes::{Buf, BytesMut};
+use actual_tokio_util::codec;
+use crate::{Encoder, KnownMinLenDecoder, ReadError};
+
+/// Adapts a decoder `D` and an encoder `E` to the `tokio_util` codec traits.
+///
+/// This allows using decoders and encoders with `Framed`, `FramedRead` and `FramedWrite`. The
+/// decoder is reset using `Default` after each decoded value. It needs to implement
+/// [`KnownMinLenDecoder`] so that the values can be returned as soon as they are complete rather
+/// than when more bytes arrive.
+///
+/// Any item convertible into `E` can be written. The encoded bytes are appended to the buffer
+/// using [`Encoder::for_each_sync`].
+pub struct Codec<D, E> {
+    decoder: D,
+    in_progress: bool,
+    _phantom: PhantomData<fn(E)>,
+}
+
+impl<D: Default, E> Codec<D, E> {
+    /// Creates the codec.
+    pub fn new() -> Self {
+        Self::with_decoder(D::default())
+    }
+}
+
+impl<D, E> Codec<D, E> {
+    /// Creates the codec using the given decoder for the first value.
+    pub fn with_decoder(decoder: D) -> Self {
+        Codec {
+            decoder,
+            in_progress: false,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<D: Default, E> Default for Codec<D, E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<D: core::fmt::Debug, E> core::fmt::Debug for Codec<D, E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("Codec")
+            .field("decoder", &self.decoder)
+            .field("in_progress", &self.in_progress)
+            .finish()
+    }
+}
+
+impl<D: KnownMinLenDecoder + Default, E> codec::Decoder for Codec<D, E> {
+    type Item = D::Value;
+    type Error = ReadError<D::Error>;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        let mut bytes = &src[..];
+        self.decoder.decode_chunk(&mut bytes).map_err(ReadError::Decode)?;
+        let consumed = src.len() - bytes.len();
+        src.advance(consumed);
+        self.in_progress |= consumed > 0;
+        // Checking in_progress prevents infinite loop with decoders of empty values.
+        if self.in_progress && self.decoder.is_at_end() {
+            self.in_progress = false;
+            return self.decoder.take().map(Some).map_err(ReadError::Decode);
+        }
+        src.reserve(self.decoder.min_required_bytes());
+        Ok(None)
+    }
+
+    fn decode_eof(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        if let Some(value) = self.decode(src)? {
+            return Ok(Some(value));
+        }
+        if self.in_progress {
+            self.in_progress = false;
+            self.decoder.take().map(Some).map_err(ReadError::Decode)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<D, E: Encoder, T: Into<E>> codec::Encoder<T> for Codec<D, E> {
+    type Error = std::io::Error;
+
+    fn encode(&mut self, item: T, dst: &mut BytesMut) -> Result<(), Self::Error> {
+        let encoder = item.into();
+        if let Some(len) = encoder.encoded_len_hint() {
+            dst.reserve(len);
+        }
+        encoder.for_each_sync(|chunk| dst.extend_from_slice(chunk));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::BytesMut;
+    use actual_tokio_util::codec::{Decoder as _, Encoder as _};
+    use super::Codec;
+    use crate::ReadError;
+    use crate::decoders::IntDecoder;
+    use crate::encoders::BytesEncoder;
+    use crate::int::BigEndian;
+
+    type U16Codec = Codec<IntDecoder<u16, BigEndian>, BytesEncoder<[u8; 2]>>;
+
+    #[test]
+    fn decode_split() {
+        let mut codec = U16Codec::new();
+        let mut buf = BytesMut::from(&[0u8, 1, 0][..]);
+        assert_eq!(codec.decode(&mut buf).unwrap(), Some(1));
+        assert_eq!(codec.decode(&mut buf).unwrap(), None);
+        assert!(buf.is_empty());
+        buf.extend_from_slice(&[2, 0]);
+        assert_eq!(codec.decode(&mut buf).unwrap(), Some(2));
+        assert_eq!(codec.decode(&mut buf).unwrap(), None);
+        assert!(buf.is_empty());
+        assert!(codec.decode_eof(&mut buf).is_err());
+    }
+
+    #[test]
+    fn decode_eof() {
+        let mut codec = U16Codec::new();
+        let mut buf = BytesMut::new();
+        assert_eq!(codec.decode_eof(&mut buf).unwrap(), None);
+        buf.extend_from_slice(&[0]);
+        assert!(matches!(codec.decode_eof(&mut buf), Err(ReadError::Decode(_))));
+    }
+
+    #[test]
+    fn encode() {
+        let mut codec = U16Codec::new();
+        let mut buf = BytesMut::new();
+        codec.encode([1, 2], &mut buf).unwrap();
+        codec.encode(BytesEncoder::new([3, 4]), &mut buf).unwrap();
+        assert_eq!(&buf[..], [1, 2, 3, 4]);
+    }
+}
@@ -0,0 +1,120 @@
+use crate::Decoder;
+
+/// Adapts a decoder to writer traits.
+///
+/// This is useful when the bytes are produced by something that pushes them into a writer. The
+/// bytes written are fed into the decoder and the decoded value can be retrieved using
+/// [`finish`](Self::finish).
+///
+/// Once the decoder doesn't accept more bytes the writes become short and eventually return zero
+/// so [`write_all`](std::io::Write::write_all) returns an error of kind
+/// [`WriteZero`](std::io::ErrorKind::WriteZero). If decoding fails the writes return an error of
+/// kind [`InvalidData`](std::io::ErrorKind::InvalidData) and the decoding error is returned from
+/// `finish`.
+#[derive(Debug)]
+pub struct DecoderWriter<D: Decoder> {
+    decoder: D,
+    done: bool,
+    error: Option<D::Error>,
+}
+
+impl<D: Decoder> DecoderWriter<D> {
+    /// Creates the writer.
+    pub fn new(decoder: D) -> Self {
+        DecoderWriter {
+            decoder,
+            done: false,
+            error: None,
+        }
+    }
+
+    /// Returns true if the decoder doesn't accept more bytes.
+    pub fn is_done(&self) -> bool {
+        self.done || self.error.is_some()
+    }
+
+    /// Ends decoding and returns the decoded value.
+    pub fn finish(self) -> Result<D::Value, D::Error> {
+        match self.error {
+            Some(error) => Err(error),
+            None => self.decoder.end(),
+        }
+    }
+}
+
+// There's no structural pinning.
+impl<D: Decoder> Unpin for DecoderWriter<D> {}
+
+impl<D: Decoder> std::io::Write for DecoderWriter<D> {
+    fn write(&mut self, bytes: &[u8]) -> std::io::Result<usize> {
+        if self.error.is_some() {
+            return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "decoding failed"));
+        }
+        if self.done {
+            return Ok(0);
+        }
+        match self.decoder.bytes_received(bytes) {
+            Ok(num) => {
+                self.done = num < bytes.len();
+                Ok(num)
+            },
+            Err(error) => {
+                self.error = Some(error);
+                Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "decoding failed"))
+            },
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<D: Decoder> actual_tokio::io::AsyncWrite for DecoderWriter<D> {
+    fn poll_write(self: core::pin::Pin<&mut Self>, _ctx: &mut core::task::Context, bytes: &[u8]) -> core::task::Poll<std::io::Result<usize>> {
+        core::task::Poll::Ready(std::io::Write::write(self.get_mut(), bytes))
+    }
+
+    fn poll_flush(self: core::pin::Pin<&mut Self>, _ctx: &mut core::task::Context) -> core::task::Poll<std::io::Result<()>> {
+        core::task::Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(self: core::pin::Pin<&mut Self>, _ctx: &mut core::task::Context) -> core::task::Poll<std::io::Result<()>> {
+        core::task::Poll::Ready(Ok(()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+    use super::DecoderWriter;
+    use crate::decoders::{IntDecoder, U8Decoder};
+    use crate::int::BigEndian;
+
+    #[test]
+    fn split_writes() {
+        let mut writer = DecoderWriter::new(IntDecoder::<u16, BigEndian>::new());
+        assert_eq!(writer.write(&[0]).unwrap(), 1);
+        assert!(!writer.is_done());
+        assert_eq!(writer.write(&[42, 21]).unwrap(), 1);
+        assert!(writer.is_done());
+        assert_eq!(writer.write(&[21]).unwrap(), 0);
+        assert_eq!(writer.finish().unwrap(), 42);
+    }
+
+    #[test]
+    fn write_all_too_long() {
+        let mut writer = DecoderWriter::new(U8Decoder::new());
+        let error = writer.write_all(&[1, 2]).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::WriteZero);
+        assert_eq!(writer.finish().unwrap(), 1);
+    }
+
+    #[test]
+    fn unexpected_end() {
+        let mut writer = DecoderWriter::new(IntDecoder::<u16, BigEndian>::new());
+        writer.write_all(&[0]).unwrap();
+        writer.finish().unwrap_err();
+    }
+}
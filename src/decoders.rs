@@ -1,24 +1,68 @@
 pub mod combinators;
 
 mod byte_array;
+mod compact_size;
 mod u8_decoder;
 mod int;
+mod float;
+mod impls;
+mod uint_n;
+mod leb128;
+mod len_prefixed;
+mod option;
+mod tagged;
+mod tuple;
 
 #[cfg(feature = "alloc")]
 mod byte_vec;
 
+#[cfg(feature = "alloc")]
+mod until_byte;
+
 #[cfg(feature = "alloc")]
 mod utf8_string;
 
+#[cfg(feature = "alloc")]
+mod vec;
+
 pub use byte_array::ByteArrayDecoder;
+pub use compact_size::CompactSizeDecoder;
 pub use u8_decoder::U8Decoder;
 pub use int::*;
+pub use float::FloatDecoder;
+pub use uint_n::UintNDecoder;
+pub use leb128::{Leb128Decoder, ZigZagLeb128Decoder};
+pub use len_prefixed::{LengthPrefixedDecoder, WithLen};
+pub use option::OptionDecoder;
+pub use option::Error as OptionDecoderError;
+pub use tagged::{Tagged, Variants};
+pub use tagged::Error as TaggedError;
+pub use tuple::{DecodeTuple, TupleDecoder};
 
 #[cfg(feature = "alloc")]
 pub use byte_vec::ByteVecDecoder;
 
+#[cfg(feature = "alloc")]
+pub use until_byte::{UntilByteDecoder, Utf8UntilByteDecoder};
+
+#[cfg(feature = "alloc")]
+pub use until_byte::Error as UntilByteError;
+
+#[cfg(feature = "alloc")]
+pub use until_byte::Utf8Error as Utf8UntilByteError;
+
 #[cfg(feature = "alloc")]
 pub use utf8_string::Utf8StringDecoder;
 
 #[cfg(feature = "alloc")]
 pub use utf8_string::Error as Utf8StringError;
+
+#[cfg(feature = "alloc")]
+pub use vec::VecDecoder;
+
+#[cfg(feature = "alloc")]
+pub use vec::Error as VecDecoderError;
+
+/// The maximum number of bytes reserved by decoders created using [`WithLen`].
+#[cfg(feature = "alloc")]
+const RESERVE_LIMIT: usize = 4096;
@@ -1,5 +1,5 @@
 use core::mem::MaybeUninit;
-use crate::{Decoder, KnownMinLenDecoder};
+use crate::{Decode, Decoder, KnownMinLenDecoder};
 use crate::error::UnexpectedEnd;
 
 #[derive(Debug)]
@@ -64,6 +64,10 @@ impl<const N: usize> KnownMinLenDecoder for ByteArrayDecoder<N> {
     }
 }
 
+impl<const N: usize> Decode for [u8; N] {
+    type Decoder = ByteArrayDecoder<N>;
+}
+
 #[cfg(test)]
 mod tests {
     use super::ByteArrayDecoder;
@@ -1,6 +1,7 @@
 use alloc::vec::Vec;
 
 use crate::{Decoder, KnownMinLenDecoder};
+use crate::decoders::WithLen;
 use crate::error::UnexpectedEnd;
 
 #[derive(Debug)]
@@ -51,6 +52,12 @@ impl KnownMinLenDecoder for ByteVecDecoder {
     }
 }
 
+impl WithLen for ByteVecDecoder {
+    fn with_len(len: usize) -> Self {
+        Self::with_reserve_limit(len, super::RESERVE_LIMIT)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::ByteVecDecoder;
@@ -1,7 +1,19 @@
 mod then;
 mod then_try;
 mod chain;
+mod map;
+mod map_err;
+mod try_map;
+mod limit;
+mod checksum;
 
 pub use then::{Then, ThenFnPtr};
 pub use then_try::{ThenTry, ThenTryFnPtr};
 pub use chain::Chain;
+pub use map::Map;
+pub use map_err::MapErr;
+pub use try_map::TryMap;
+pub use limit::Limit;
+pub use limit::Error as LimitError;
+pub use checksum::VerifyChecksum;
+pub use checksum::Error as ChecksumError;
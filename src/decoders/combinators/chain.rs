@@ -10,6 +10,12 @@ impl<First: Decoder, Second: Decoder> Chain<First, Second> {
     }
 }
 
+impl<First: Decoder + Default, Second: Decoder + Default> Default for Chain<First, Second> {
+    fn default() -> Self {
+        Chain::new(Default::default(), Default::default())
+    }
+}
+
 impl <First, Second> fmt::Debug for Chain<First, Second>
 where First: Decoder + fmt::Debug,
       Second: Decoder + fmt::Debug,
@@ -0,0 +1,247 @@
+use core::fmt;
+use crate::{Decoder, KnownMinLenDecoder};
+use crate::checksum::Checksum;
+use crate::error::UnexpectedEnd;
+
+/// Decoder returned by [`Decoder::verify_checksum`].
+pub struct VerifyChecksum<D: Decoder, C: Checksum>(State<D, C>);
+
+enum State<D: Decoder, C: Checksum> {
+    Value(D, C),
+    Checksum {
+        value: D::Value,
+        expected: C::Digest,
+        // Only `received[..pos]` is valid, initialized by cloning `expected`.
+        received: C::Digest,
+        pos: usize,
+    },
+    Done(D::Value),
+    Errored,
+}
+
+impl<D: Decoder, C: Checksum> VerifyChecksum<D, C> {
+    pub(crate) fn new(decoder: D, checksum: C) -> Self {
+        VerifyChecksum(State::Value(decoder, checksum))
+    }
+
+    fn checksum_state(decoder: D, checksum: C) -> Result<State<D, C>, Error<D::Error, C::Digest>> {
+        let value = decoder.end().map_err(Error::Decode)?;
+        let expected = checksum.finalize();
+        debug_assert_eq!(expected.as_ref().len(), C::LEN, "the digest length doesn't match `Checksum::LEN`");
+        let received = expected.clone();
+        Ok(State::Checksum { value, expected, received, pos: 0 })
+    }
+}
+
+impl<D, C> fmt::Debug for VerifyChecksum<D, C>
+where D: Decoder + fmt::Debug,
+      D::Value: fmt::Debug,
+      C: Checksum + fmt::Debug,
+      C::Digest: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.0 {
+            State::Value(decoder, checksum) => f.debug_tuple("VerifyChecksum::Value").field(decoder).field(checksum).finish(),
+            State::Checksum { value, expected, pos, .. } => f.debug_struct("VerifyChecksum::Checksum")
+                .field("value", value)
+                .field("expected", expected)
+                .field("pos", pos)
+                .finish(),
+            State::Done(value) => f.debug_tuple("VerifyChecksum::Done").field(value).finish(),
+            State::Errored => f.debug_tuple("VerifyChecksum::Errored").finish(),
+        }
+    }
+}
+
+impl<D: Decoder, C: Checksum> Decoder for VerifyChecksum<D, C> {
+    type Value = D::Value;
+    type Error = Error<D::Error, C::Digest>;
+
+    fn decode_chunk(&mut self, bytes: &mut &[u8]) -> Result<(), Self::Error> {
+        if let State::Value(decoder, checksum) = &mut self.0 {
+            let before = *bytes;
+            let result = decoder.decode_chunk(bytes);
+            checksum.update(&before[..(before.len() - bytes.len())]);
+            result.map_err(Error::Decode)?;
+            if bytes.is_empty() {
+                return Ok(());
+            }
+            let (decoder, checksum) = match core::mem::replace(&mut self.0, State::Errored) {
+                State::Value(decoder, checksum) => (decoder, checksum),
+                _ => unreachable!(),
+            };
+            self.0 = Self::checksum_state(decoder, checksum)?;
+        }
+        match &mut self.0 {
+            State::Checksum { expected, received, pos, .. } => {
+                let received_bytes = received.as_mut();
+                let to_copy = bytes.len().min(received_bytes.len() - *pos);
+                received_bytes[*pos..(*pos + to_copy)].copy_from_slice(&bytes[..to_copy]);
+                *pos += to_copy;
+                *bytes = &bytes[to_copy..];
+                if *pos < received_bytes.len() {
+                    return Ok(());
+                }
+                if expected.as_ref() != received.as_ref() {
+                    let (expected, received) = match core::mem::replace(&mut self.0, State::Errored) {
+                        State::Checksum { expected, received, .. } => (expected, received),
+                        _ => unreachable!(),
+                    };
+                    return Err(Error::Mismatch { expected, received });
+                }
+                self.0 = match core::mem::replace(&mut self.0, State::Errored) {
+                    State::Checksum { value, .. } => State::Done(value),
+                    _ => unreachable!(),
+                };
+                Ok(())
+            },
+            State::Done(_) => Ok(()),
+            State::Value(_, _) => unreachable!(),
+            State::Errored => panic!("use of failed decoder"),
+        }
+    }
+
+    fn end(self) -> Result<Self::Value, Self::Error> {
+        let state = match self.0 {
+            State::Value(decoder, checksum) => Self::checksum_state(decoder, checksum)?,
+            state => state,
+        };
+        match state {
+            State::Checksum { expected, pos, .. } => {
+                Err(Error::UnexpectedEnd(UnexpectedEnd { missing: expected.as_ref().len() - pos }))
+            },
+            State::Done(value) => Ok(value),
+            State::Value(_, _) => unreachable!(),
+            State::Errored => panic!("use of failed decoder"),
+        }
+    }
+}
+
+impl<D: KnownMinLenDecoder, C: Checksum> KnownMinLenDecoder for VerifyChecksum<D, C> {
+    fn min_required_bytes(&self) -> usize {
+        match &self.0 {
+            State::Value(decoder, _) => decoder.min_required_bytes().saturating_add(C::LEN),
+            State::Checksum { expected, pos, .. } => expected.as_ref().len() - pos,
+            State::Done(_) => 0,
+            State::Errored => panic!("use of failed decoder"),
+        }
+    }
+}
+
+/// Error returned by [`VerifyChecksum`].
+#[derive(Debug, Clone)]
+pub enum Error<E, D> {
+    /// The inner decoder failed.
+    Decode(E),
+    /// The stream ended before the whole checksum was received.
+    UnexpectedEnd(UnexpectedEnd),
+    /// The received checksum doesn't match the checksum of the decoded bytes.
+    Mismatch {
+        /// The checksum computed from the decoded bytes.
+        expected: D,
+        /// The checksum found in the stream.
+        received: D,
+    },
+}
+
+impl<E, D> fmt::Display for Error<E, D> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Decode(_) => write!(f, "failed to decode the checksummed value"),
+            Error::UnexpectedEnd(_) => write!(f, "unexpected end"),
+            Error::Mismatch { .. } => write!(f, "checksum mismatch"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: std::error::Error + 'static, D: fmt::Debug> std::error::Error for Error<E, D> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Decode(error) => Some(error),
+            Error::UnexpectedEnd(error) => Some(error),
+            Error::Mismatch { .. } => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Error;
+    use crate::{Decoder, KnownMinLenDecoder};
+    use crate::checksum::Checksum;
+    use crate::decoders::{ByteArrayDecoder, U16BeDecoder};
+    use crate::test_utils::Sum;
+
+    #[test]
+    fn valid() {
+        let mut observed = [0; 16];
+        let mut decoder = ByteArrayDecoder::<3>::new().verify_checksum(Sum(0, Some(&mut observed), 0));
+        assert_eq!(decoder.min_required_bytes(), 5);
+        assert_eq!(decoder.bytes_received(&[1, 2]).unwrap(), 2);
+        assert_eq!(decoder.min_required_bytes(), 3);
+        assert_eq!(decoder.bytes_received(&[3, 0]).unwrap(), 2);
+        assert_eq!(decoder.min_required_bytes(), 1);
+        assert_eq!(decoder.bytes_received(&[6, 42]).unwrap(), 1);
+        assert_eq!(decoder.min_required_bytes(), 0);
+        assert_eq!(decoder.end().unwrap(), [1, 2, 3]);
+        assert_eq!(observed[..4], [1, 2, 3, 0]);
+    }
+
+    #[test]
+    fn observes_consumed_bytes_only() {
+        let mut observed = [0; 16];
+        let mut decoder = U16BeDecoder::new().verify_checksum(Sum(0, Some(&mut observed), 0));
+        assert_eq!(decoder.bytes_received(&[1, 2, 0, 3]).unwrap(), 4);
+        assert_eq!(decoder.end().unwrap(), 0x0102);
+        assert_eq!(observed[..3], [1, 2, 0]);
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "the digest length doesn't match")]
+    fn invalid_len() {
+        struct Invalid;
+
+        impl Checksum for Invalid {
+            type Digest = [u8; 1];
+            const LEN: usize = 2;
+
+            fn update(&mut self, _: &[u8]) {}
+
+            fn finalize(self) -> Self::Digest {
+                [0]
+            }
+        }
+
+        let mut decoder = U16BeDecoder::new().verify_checksum(Invalid);
+        let _ = decoder.bytes_received(&[1, 2, 0]);
+    }
+
+    #[test]
+    fn mismatch() {
+        let mut decoder = U16BeDecoder::new().verify_checksum(Sum::default());
+        match decoder.bytes_received(&[1, 2, 0, 4]) {
+            Err(Error::Mismatch { expected, received }) => {
+                assert_eq!(expected, [0, 3]);
+                assert_eq!(received, [0, 4]);
+            },
+            _ => panic!("expected mismatch"),
+        }
+    }
+
+    #[test]
+    fn truncated() {
+        let mut decoder = U16BeDecoder::new().verify_checksum(Sum::default());
+        assert_eq!(decoder.bytes_received(&[1, 2]).unwrap(), 2);
+        assert!(matches!(decoder.end(), Err(Error::UnexpectedEnd(error)) if error.missing == 2));
+
+        let mut decoder = U16BeDecoder::new().verify_checksum(Sum::default());
+        assert_eq!(decoder.bytes_received(&[1, 2, 0]).unwrap(), 3);
+        assert!(matches!(decoder.end(), Err(Error::UnexpectedEnd(error)) if error.missing == 1));
+
+        let mut decoder = U16BeDecoder::new().verify_checksum(Sum::default());
+        assert_eq!(decoder.bytes_received(&[1]).unwrap(), 1);
+        assert!(matches!(decoder.end(), Err(Error::Decode(_))));
+    }
+}
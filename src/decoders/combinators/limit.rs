@@ -0,0 +1,138 @@
+use core::fmt;
+use crate::{Decoder, KnownMinLenDecoder};
+use crate::error::UnexpectedEnd;
+
+/// Decoder returned by [`Decoder::limit`].
+#[derive(Debug)]
+pub struct Limit<D: Decoder> {
+    decoder: D,
+    remaining: usize,
+}
+
+impl<D: Decoder> Limit<D> {
+    pub(crate) fn new(decoder: D, limit: usize) -> Self {
+        Limit {
+            decoder,
+            remaining: limit,
+        }
+    }
+
+    /// Returns the number of bytes that still have to be consumed.
+    pub fn remaining(&self) -> usize {
+        self.remaining
+    }
+}
+
+impl<D: Decoder> Decoder for Limit<D> {
+    type Value = D::Value;
+    type Error = Error<D::Error>;
+
+    fn decode_chunk(&mut self, bytes: &mut &[u8]) -> Result<(), Self::Error> {
+        let len = bytes.len().min(self.remaining);
+        let mut limited = &bytes[..len];
+        self.decoder.decode_chunk(&mut limited).map_err(Error::Decode)?;
+        let consumed = len - limited.len();
+        self.remaining -= consumed;
+        *bytes = &bytes[consumed..];
+        // The inner decoder didn't consume all bytes it was given so it's done.
+        if !limited.is_empty() {
+            return Err(Error::Underrun { remaining: self.remaining });
+        }
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Value, Self::Error> {
+        if self.remaining > 0 {
+            return Err(Error::UnexpectedEnd(UnexpectedEnd { missing: self.remaining }));
+        }
+        self.decoder.end().map_err(Error::Overrun)
+    }
+}
+
+impl<D: KnownMinLenDecoder> KnownMinLenDecoder for Limit<D> {
+    fn min_required_bytes(&self) -> usize {
+        self.decoder.min_required_bytes().min(self.remaining)
+    }
+}
+
+/// Error returned by [`Limit`].
+#[derive(Debug, Clone)]
+pub enum Error<E> {
+    /// The inner decoder failed before reaching the limit.
+    Decode(E),
+    /// The inner decoder finished before consuming all bytes.
+    Underrun {
+        /// The number of bytes left unconsumed.
+        remaining: usize,
+    },
+    /// The inner decoder failed after consuming all bytes.
+    ///
+    /// This usually means the inner decoder needed more bytes than the limit allows.
+    Overrun(E),
+    /// The input ended before the limit was reached.
+    UnexpectedEnd(UnexpectedEnd),
+}
+
+impl<E> fmt::Display for Error<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Decode(_) => write!(f, "failed to decode the limited value"),
+            Error::Underrun { remaining } => write!(f, "the limited value ended {} bytes before the limit", remaining),
+            Error::Overrun(_) => write!(f, "the limited value didn't end at the limit"),
+            Error::UnexpectedEnd(_) => write!(f, "unexpected end"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: std::error::Error + 'static> std::error::Error for Error<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Decode(error) | Error::Overrun(error) => Some(error),
+            Error::Underrun { .. } => None,
+            Error::UnexpectedEnd(error) => Some(error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Error;
+    use crate::{Decoder, KnownMinLenDecoder};
+    use crate::decoders::{IntDecoder, U8Decoder};
+    use crate::int::BigEndian;
+
+    type U16Decoder = IntDecoder<u16, BigEndian>;
+
+    #[test]
+    fn exact() {
+        let mut decoder = U16Decoder::new().limit(2);
+        assert_eq!(decoder.min_required_bytes(), 2);
+        assert_eq!(decoder.bytes_received(&[0]).unwrap(), 1);
+        assert_eq!(decoder.min_required_bytes(), 1);
+        assert_eq!(decoder.bytes_received(&[42, 21]).unwrap(), 1);
+        assert_eq!(decoder.min_required_bytes(), 0);
+        assert_eq!(decoder.end().unwrap(), 42);
+    }
+
+    #[test]
+    fn underrun() {
+        let mut decoder = U8Decoder::new().limit(2);
+        assert!(matches!(decoder.bytes_received(&[1, 2, 3]), Err(Error::Underrun { remaining: 1 })));
+    }
+
+    #[test]
+    fn overrun() {
+        let mut decoder = U16Decoder::new().limit(1);
+        assert_eq!(decoder.min_required_bytes(), 1);
+        assert_eq!(decoder.bytes_received(&[0, 42]).unwrap(), 1);
+        assert!(matches!(decoder.end(), Err(Error::Overrun(_))));
+    }
+
+    #[test]
+    fn truncated() {
+        let mut decoder = U8Decoder::new().limit(2);
+        assert_eq!(decoder.bytes_received(&[1]).unwrap(), 1);
+        assert!(matches!(decoder.end(), Err(Error::UnexpectedEnd(_))));
+    }
+}
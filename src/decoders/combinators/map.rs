@@ -0,0 +1,56 @@
+use crate::{Decoder, KnownMinLenDecoder};
+
+/// Decoder returned by [`Decoder::map`].
+#[derive(Debug)]
+pub struct Map<D: Decoder, F: FnOnce(D::Value) -> T, T> {
+    decoder: D,
+    fun: F,
+}
+
+impl<D: Decoder, F: FnOnce(D::Value) -> T, T> Map<D, F, T> {
+    pub(crate) fn new(decoder: D, fun: F) -> Self {
+        Map {
+            decoder,
+            fun,
+        }
+    }
+}
+
+impl<D: Decoder, F: FnOnce(D::Value) -> T, T> Decoder for Map<D, F, T> {
+    type Value = T;
+    type Error = D::Error;
+
+    #[inline]
+    fn decode_chunk(&mut self, bytes: &mut &[u8]) -> Result<(), Self::Error> {
+        self.decoder.decode_chunk(bytes)
+    }
+
+    fn end(self) -> Result<Self::Value, Self::Error> {
+        self.decoder.end().map(self.fun)
+    }
+}
+
+impl<D: KnownMinLenDecoder, F: FnOnce(D::Value) -> T, T> KnownMinLenDecoder for Map<D, F, T> {
+    fn min_required_bytes(&self) -> usize {
+        self.decoder.min_required_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Decoder;
+    use crate::decoders::U8Decoder;
+
+    #[test]
+    fn map() {
+        let mut decoder = U8Decoder::new().map(u32::from);
+        assert_eq!(decoder.bytes_received(&[42, 21]).unwrap(), 1);
+        assert_eq!(decoder.end().unwrap(), 42u32);
+    }
+
+    #[test]
+    fn map_empty() {
+        let decoder = U8Decoder::new().map(u32::from);
+        decoder.end().unwrap_err();
+    }
+}
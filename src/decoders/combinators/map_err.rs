@@ -0,0 +1,56 @@
+use crate::{Decoder, KnownMinLenDecoder};
+
+/// Decoder returned by [`Decoder::map_err`].
+#[derive(Debug)]
+pub struct MapErr<D: Decoder, F: FnMut(D::Error) -> E, E> {
+    decoder: D,
+    fun: F,
+}
+
+impl<D: Decoder, F: FnMut(D::Error) -> E, E> MapErr<D, F, E> {
+    pub(crate) fn new(decoder: D, fun: F) -> Self {
+        MapErr {
+            decoder,
+            fun,
+        }
+    }
+}
+
+impl<D: Decoder, F: FnMut(D::Error) -> E, E> Decoder for MapErr<D, F, E> {
+    type Value = D::Value;
+    type Error = E;
+
+    #[inline]
+    fn decode_chunk(&mut self, bytes: &mut &[u8]) -> Result<(), Self::Error> {
+        self.decoder.decode_chunk(bytes).map_err(&mut self.fun)
+    }
+
+    fn end(self) -> Result<Self::Value, Self::Error> {
+        self.decoder.end().map_err(self.fun)
+    }
+}
+
+impl<D: KnownMinLenDecoder, F: FnMut(D::Error) -> E, E> KnownMinLenDecoder for MapErr<D, F, E> {
+    fn min_required_bytes(&self) -> usize {
+        self.decoder.min_required_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Decoder;
+    use crate::decoders::U8Decoder;
+
+    #[test]
+    fn map_err() {
+        let decoder = U8Decoder::new().map_err(|_| "no byte");
+        assert_eq!(decoder.end().unwrap_err(), "no byte");
+    }
+
+    #[test]
+    fn map_err_ok() {
+        let mut decoder = U8Decoder::new().map_err(|_| "no byte");
+        assert_eq!(decoder.bytes_received(&[42]).unwrap(), 1);
+        assert_eq!(decoder.end().unwrap(), 42);
+    }
+}
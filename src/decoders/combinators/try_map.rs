@@ -0,0 +1,84 @@
+use crate::{Decoder, KnownMinLenDecoder};
+
+/// Decoder returned by [`Decoder::try_map`].
+#[derive(Debug)]
+pub struct TryMap<E, D: Decoder, F: FnOnce(D::Value) -> Result<T, E>, T> where E: From<D::Error> {
+    decoder: D,
+    fun: F,
+}
+
+impl<E, D: Decoder, F: FnOnce(D::Value) -> Result<T, E>, T> TryMap<E, D, F, T> where E: From<D::Error> {
+    pub(crate) fn new(decoder: D, fun: F) -> Self {
+        TryMap {
+            decoder,
+            fun,
+        }
+    }
+}
+
+impl<E, D: Decoder, F: FnOnce(D::Value) -> Result<T, E>, T> Decoder for TryMap<E, D, F, T> where E: From<D::Error> {
+    type Value = T;
+    type Error = E;
+
+    #[inline]
+    fn decode_chunk(&mut self, bytes: &mut &[u8]) -> Result<(), Self::Error> {
+        self.decoder.decode_chunk(bytes).map_err(Into::into)
+    }
+
+    fn end(self) -> Result<Self::Value, Self::Error> {
+        (self.fun)(self.decoder.end()?)
+    }
+}
+
+impl<E, D: KnownMinLenDecoder, F: FnOnce(D::Value) -> Result<T, E>, T> KnownMinLenDecoder for TryMap<E, D, F, T> where E: From<D::Error> {
+    fn min_required_bytes(&self) -> usize {
+        self.decoder.min_required_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Decoder;
+    use crate::decoders::U8Decoder;
+    use crate::error::UnexpectedEnd;
+
+    #[derive(Debug)]
+    enum Error {
+        UnexpectedEnd,
+        InvalidBool(u8),
+    }
+
+    impl From<UnexpectedEnd> for Error {
+        fn from(_: UnexpectedEnd) -> Self {
+            Error::UnexpectedEnd
+        }
+    }
+
+    fn decode_bool(byte: u8) -> Result<bool, Error> {
+        match byte {
+            0 => Ok(false),
+            1 => Ok(true),
+            byte => Err(Error::InvalidBool(byte)),
+        }
+    }
+
+    #[test]
+    fn try_map() {
+        let mut decoder = U8Decoder::new().try_map(decode_bool);
+        assert_eq!(decoder.bytes_received(&[1, 21]).unwrap(), 1);
+        assert!(decoder.end().unwrap());
+    }
+
+    #[test]
+    fn try_map_invalid() {
+        let mut decoder = U8Decoder::new().try_map(decode_bool);
+        assert_eq!(decoder.bytes_received(&[2]).unwrap(), 1);
+        assert!(matches!(decoder.end(), Err(Error::InvalidBool(2))));
+    }
+
+    #[test]
+    fn try_map_empty() {
+        let decoder = U8Decoder::new().try_map(decode_bool);
+        assert!(matches!(decoder.end(), Err(Error::UnexpectedEnd)));
+    }
+}
@@ -0,0 +1,171 @@
+use crate::{Decoder, KnownMinLenDecoder};
+use crate::error::{CompactSizeError, UnexpectedEnd};
+
+/// Decodes Bitcoin CompactSize integer.
+///
+/// Non-minimal encodings are rejected. The decoded value is commonly used as a length prefix,
+/// e.g. `CompactSizeDecoder::new().then(|len| ByteVecDecoder::new(len as usize))`.
+#[derive(Debug)]
+pub struct CompactSizeDecoder(State);
+
+impl CompactSizeDecoder {
+    pub fn new() -> Self {
+        CompactSizeDecoder(State::Prefix)
+    }
+}
+
+impl Default for CompactSizeDecoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug)]
+enum State {
+    Prefix,
+    Payload { buf: [u8; 8], required: u8, len: u8 },
+    Done(u64),
+}
+
+impl Decoder for CompactSizeDecoder {
+    type Value = u64;
+    type Error = CompactSizeError;
+
+    fn decode_chunk(&mut self, bytes: &mut &[u8]) -> Result<(), Self::Error> {
+        loop {
+            match &mut self.0 {
+                State::Prefix => {
+                    let prefix = match bytes.first() {
+                        Some(prefix) => *prefix,
+                        None => return Ok(()),
+                    };
+                    *bytes = &bytes[1..];
+                    let required = match prefix {
+                        0xFD => 2,
+                        0xFE => 4,
+                        0xFF => 8,
+                        value => {
+                            self.0 = State::Done(value.into());
+                            return Ok(());
+                        },
+                    };
+                    self.0 = State::Payload { buf: [0; 8], required, len: 0 };
+                },
+                State::Payload { buf, required, len } => {
+                    let to_copy = bytes.len().min(usize::from(*required - *len));
+                    let start = usize::from(*len);
+                    buf[start..(start + to_copy)].copy_from_slice(&bytes[..to_copy]);
+                    *len += to_copy as u8;
+                    *bytes = &bytes[to_copy..];
+                    if *len < *required {
+                        return Ok(());
+                    }
+                    let value = u64::from_le_bytes(*buf);
+                    let min = match *required {
+                        2 => 0xFD,
+                        4 => 0x10000,
+                        _ => 0x100000000,
+                    };
+                    if value < min {
+                        return Err(CompactSizeError::NonMinimal { value });
+                    }
+                    self.0 = State::Done(value);
+                },
+                State::Done(_) => return Ok(()),
+            }
+        }
+    }
+
+    fn end(self) -> Result<Self::Value, Self::Error> {
+        match self.0 {
+            State::Prefix => Err(CompactSizeError::UnexpectedEnd(UnexpectedEnd { missing: 1 })),
+            State::Payload { required, len, .. } => Err(CompactSizeError::UnexpectedEnd(UnexpectedEnd { missing: (required - len).into() })),
+            State::Done(value) => Ok(value),
+        }
+    }
+}
+
+impl KnownMinLenDecoder for CompactSizeDecoder {
+    fn min_required_bytes(&self) -> usize {
+        match &self.0 {
+            State::Prefix => 1,
+            State::Payload { required, len, .. } => (required - len).into(),
+            State::Done(_) => 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CompactSizeDecoder;
+    use crate::{Decoder, KnownMinLenDecoder};
+    use crate::error::CompactSizeError;
+
+    #[test]
+    fn empty() {
+        let decoder = CompactSizeDecoder::new();
+        decoder.end().unwrap_err();
+    }
+
+    #[test]
+    fn one() {
+        let mut decoder = CompactSizeDecoder::new();
+        assert_eq!(decoder.bytes_received(&[0xFC, 42]).unwrap(), 1);
+        assert_eq!(decoder.end().unwrap(), 0xFC);
+    }
+
+    #[test]
+    fn u16() {
+        let mut decoder = CompactSizeDecoder::new();
+        assert_eq!(decoder.bytes_received(&[0xFD, 0xFD, 0x00, 42]).unwrap(), 3);
+        assert_eq!(decoder.end().unwrap(), 0xFD);
+    }
+
+    #[test]
+    fn u32_split() {
+        let mut decoder = CompactSizeDecoder::new();
+        assert_eq!(decoder.bytes_received(&[0xFE]).unwrap(), 1);
+        assert_eq!(decoder.min_required_bytes(), 4);
+        assert_eq!(decoder.bytes_received(&[0x00, 0x00]).unwrap(), 2);
+        assert_eq!(decoder.min_required_bytes(), 2);
+        assert_eq!(decoder.bytes_received(&[0x01, 0x00, 42]).unwrap(), 2);
+        assert_eq!(decoder.end().unwrap(), 0x10000);
+    }
+
+    #[test]
+    fn u64() {
+        let mut decoder = CompactSizeDecoder::new();
+        assert_eq!(decoder.bytes_received(&[0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF]).unwrap(), 9);
+        assert_eq!(decoder.end().unwrap(), u64::MAX);
+    }
+
+    #[test]
+    fn truncated() {
+        let mut decoder = CompactSizeDecoder::new();
+        assert_eq!(decoder.bytes_received(&[0xFE, 0x00]).unwrap(), 2);
+        assert!(matches!(decoder.end(), Err(CompactSizeError::UnexpectedEnd(_))));
+    }
+
+    #[test]
+    fn non_minimal() {
+        let cases: &[&[u8]] = &[
+            &[0xFD, 0xFC, 0x00],
+            &[0xFE, 0xFF, 0xFF, 0x00, 0x00],
+            &[0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x00, 0x00, 0x00, 0x00],
+        ];
+        for bytes in cases {
+            let mut decoder = CompactSizeDecoder::new();
+            assert!(matches!(decoder.bytes_received(bytes), Err(CompactSizeError::NonMinimal { .. })));
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn length_prefix() {
+        use crate::decoders::ByteVecDecoder;
+
+        let mut decoder = CompactSizeDecoder::new().then(|len| ByteVecDecoder::new(len as usize));
+        assert_eq!(decoder.bytes_received(&[2, 3, 4, 5]).unwrap(), 3);
+        assert_eq!(decoder.end().unwrap(), vec![3, 4]);
+    }
+}
@@ -0,0 +1,84 @@
+use core::fmt;
+use core::marker::PhantomData;
+use crate::{Decode, Decoder, KnownMinLenDecoder};
+use crate::decoders::IntDecoder;
+use crate::error::UnexpectedEnd;
+use crate::int::*;
+
+/// Decodes a floating-point number from its bit representation in the given byte order.
+pub struct FloatDecoder<T: Float, Endian: ByteOrder>(IntDecoder<T::Bits, Endian>, PhantomData<fn() -> T>);
+
+impl<T: Float, Endian: ByteOrder> FloatDecoder<T, Endian> {
+    pub fn new() -> Self {
+        FloatDecoder(IntDecoder::new(), PhantomData)
+    }
+}
+
+impl<T: Float, Endian: ByteOrder> Default for FloatDecoder<T, Endian> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float, Endian: ByteOrder> fmt::Debug for FloatDecoder<T, Endian> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("FloatDecoder").field(&self.0).finish()
+    }
+}
+
+impl<T: Float, Endian: ByteOrder> Decoder for FloatDecoder<T, Endian> {
+    type Value = T;
+    type Error = UnexpectedEnd;
+
+    fn decode_chunk(&mut self, bytes: &mut &[u8]) -> Result<(), Self::Error> {
+        self.0.decode_chunk(bytes)
+    }
+
+    fn end(self) -> Result<Self::Value, Self::Error> {
+        self.0.end().map(T::from_bits)
+    }
+}
+
+impl<T: Float, Endian: ByteOrder> KnownMinLenDecoder for FloatDecoder<T, Endian> {
+    fn min_required_bytes(&self) -> usize {
+        self.0.min_required_bytes()
+    }
+}
+
+impl Decode for f32 {
+    type Decoder = FloatDecoder<f32, BigEndian>;
+}
+
+impl Decode for f64 {
+    type Decoder = FloatDecoder<f64, BigEndian>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FloatDecoder;
+    use crate::{Decoder, KnownMinLenDecoder};
+    use crate::int::{BigEndian, LittleEndian};
+
+    #[test]
+    fn f32_be() {
+        let mut decoder = FloatDecoder::<f32, BigEndian>::new();
+        assert_eq!(decoder.bytes_received(&[0x3F, 0x80]).unwrap(), 2);
+        assert_eq!(decoder.min_required_bytes(), 2);
+        assert_eq!(decoder.bytes_received(&[0, 0, 42]).unwrap(), 2);
+        assert_eq!(decoder.end().unwrap(), 1.0);
+    }
+
+    #[test]
+    fn f64_le() {
+        let mut decoder = FloatDecoder::<f64, LittleEndian>::new();
+        assert_eq!(decoder.bytes_received(&(-2.5f64).to_le_bytes()).unwrap(), 8);
+        assert_eq!(decoder.end().unwrap(), -2.5);
+    }
+
+    #[test]
+    fn truncated() {
+        let mut decoder = FloatDecoder::<f64, BigEndian>::new();
+        assert_eq!(decoder.bytes_received(&[0; 7]).unwrap(), 7);
+        assert_eq!(decoder.end().unwrap_err().missing, 1);
+    }
+}
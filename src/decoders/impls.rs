@@ -0,0 +1,94 @@
+//! Implementations of decoder traits for foreign types.
+
+use either::Either;
+use crate::{Decoder, KnownMinLenDecoder};
+
+/// Decodes using either of the decoders.
+///
+/// This is useful when the decoder is chosen at runtime, e.g. in [`then`](Decoder::then).
+impl<L: Decoder, R: Decoder<Value = L::Value>> Decoder for Either<L, R> {
+    type Value = L::Value;
+    type Error = Either<L::Error, R::Error>;
+
+    fn decode_chunk(&mut self, bytes: &mut &[u8]) -> Result<(), Self::Error> {
+        match self {
+            Either::Left(decoder) => decoder.decode_chunk(bytes).map_err(Either::Left),
+            Either::Right(decoder) => decoder.decode_chunk(bytes).map_err(Either::Right),
+        }
+    }
+
+    fn end(self) -> Result<Self::Value, Self::Error> {
+        match self {
+            Either::Left(decoder) => decoder.end().map_err(Either::Left),
+            Either::Right(decoder) => decoder.end().map_err(Either::Right),
+        }
+    }
+}
+
+impl<L: KnownMinLenDecoder, R: KnownMinLenDecoder<Value = L::Value>> KnownMinLenDecoder for Either<L, R> {
+    fn min_required_bytes(&self) -> usize {
+        match self {
+            Either::Left(decoder) => decoder.min_required_bytes(),
+            Either::Right(decoder) => decoder.min_required_bytes(),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<D: Decoder> Decoder for alloc::boxed::Box<D> {
+    type Value = D::Value;
+    type Error = D::Error;
+
+    fn decode_chunk(&mut self, bytes: &mut &[u8]) -> Result<(), Self::Error> {
+        (**self).decode_chunk(bytes)
+    }
+
+    fn end(self) -> Result<Self::Value, Self::Error> {
+        (*self).end()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<D: KnownMinLenDecoder> KnownMinLenDecoder for alloc::boxed::Box<D> {
+    fn min_required_bytes(&self) -> usize {
+        (**self).min_required_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use either::Either;
+    use crate::{Decoder, KnownMinLenDecoder};
+    use crate::decoders::{ByteArrayDecoder, U8Decoder};
+
+    #[test]
+    fn either_in_then() {
+        let decoder = || U8Decoder::new().then(|tag| {
+            if tag == 0 {
+                Either::Left(U8Decoder::new().map(|byte| [byte, 0]))
+            } else {
+                Either::Right(ByteArrayDecoder::<2>::new())
+            }
+        });
+
+        let mut first = decoder();
+        assert_eq!(first.bytes_received(&[0, 42, 21]).unwrap(), 2);
+        assert_eq!(first.end().unwrap(), [42, 0]);
+
+        let mut second = decoder();
+        assert_eq!(second.bytes_received(&[1, 42]).unwrap(), 2);
+        assert_eq!(second.min_required_bytes(), 1);
+        assert_eq!(second.bytes_received(&[21]).unwrap(), 1);
+        assert_eq!(second.end().unwrap(), [42, 21]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn boxed() {
+        let mut decoder = alloc::boxed::Box::new(ByteArrayDecoder::<2>::new());
+        assert_eq!(decoder.bytes_received(&[42]).unwrap(), 1);
+        assert_eq!(decoder.min_required_bytes(), 1);
+        assert_eq!(decoder.bytes_received(&[21]).unwrap(), 1);
+        assert_eq!(decoder.end().unwrap(), [42, 21]);
+    }
+}
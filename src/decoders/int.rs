@@ -1,9 +1,10 @@
 use core::fmt;
 use core::marker::PhantomData;
-use crate::{Decoder, KnownMinLenDecoder};
+use crate::{Decode, Decoder, KnownMinLenDecoder};
 use crate::error::UnexpectedEnd;
 use crate::int::*;
 
+/// Decodes an integer from an array of bytes in the given byte order.
 pub struct IntDecoder<T: Int, Endian: ByteOrder>(T::InnerDecoder, PhantomData<fn() -> T>, PhantomData<Endian>);
 
 impl<T: Int, Endian: ByteOrder> IntDecoder<T, Endian> {
@@ -24,7 +25,7 @@ impl<T: Int, Endian: ByteOrder> fmt::Debug for IntDecoder<T, Endian> {
     }
 }
 
-impl<T: Int> Decoder for IntDecoder<T, BigEndian> {
+impl<T: Int, Endian: ByteOrder> Decoder for IntDecoder<T, Endian> {
     type Value = T;
     type Error = UnexpectedEnd;
 
@@ -33,25 +34,88 @@ impl<T: Int> Decoder for IntDecoder<T, BigEndian> {
     }
 
     fn end(self) -> Result<Self::Value, Self::Error> {
-        self.0.end().map(Int::from_be_bytes)
+        self.0.end().map(Endian::from_bytes)
     }
 }
 
-impl<T: Int> Decoder for IntDecoder<T, LittleEndian> {
-    type Value = T;
-    type Error = UnexpectedEnd;
-
-    fn decode_chunk(&mut self, bytes: &mut &[u8]) -> Result<(), Self::Error> {
-        self.0.decode_chunk(bytes)
+impl<T: Int, Endian: ByteOrder> KnownMinLenDecoder for IntDecoder<T, Endian> {
+    fn min_required_bytes(&self) -> usize {
+        self.0.min_required_bytes()
     }
+}
 
-    fn end(self) -> Result<Self::Value, Self::Error> {
-        self.0.end().map(Int::from_le_bytes)
+macro_rules! impl_decode {
+    ($($int:ty),+) => {
+        $(
+            impl Decode for $int {
+                type Decoder = IntDecoder<$int, BigEndian>;
+            }
+        )+
     }
 }
 
-impl<T: Int> KnownMinLenDecoder for IntDecoder<T, BigEndian> {
-    fn min_required_bytes(&self) -> usize {
-        self.0.min_required_bytes()
+impl_decode!(u8, i8, u16, i16, u32, i32, u64, i64, u128, i128);
+
+/// Decodes little-endian `u16`.
+pub type U16LeDecoder = IntDecoder<u16, LittleEndian>;
+/// Decodes big-endian `u16`.
+pub type U16BeDecoder = IntDecoder<u16, BigEndian>;
+/// Decodes little-endian `u32`.
+pub type U32LeDecoder = IntDecoder<u32, LittleEndian>;
+/// Decodes big-endian `u32`.
+pub type U32BeDecoder = IntDecoder<u32, BigEndian>;
+/// Decodes little-endian `u64`.
+pub type U64LeDecoder = IntDecoder<u64, LittleEndian>;
+/// Decodes big-endian `u64`.
+pub type U64BeDecoder = IntDecoder<u64, BigEndian>;
+/// Decodes little-endian `u128`.
+pub type U128LeDecoder = IntDecoder<u128, LittleEndian>;
+/// Decodes big-endian `u128`.
+pub type U128BeDecoder = IntDecoder<u128, BigEndian>;
+/// Decodes little-endian `i16`.
+pub type I16LeDecoder = IntDecoder<i16, LittleEndian>;
+/// Decodes big-endian `i16`.
+pub type I16BeDecoder = IntDecoder<i16, BigEndian>;
+/// Decodes little-endian `i32`.
+pub type I32LeDecoder = IntDecoder<i32, LittleEndian>;
+/// Decodes big-endian `i32`.
+pub type I32BeDecoder = IntDecoder<i32, BigEndian>;
+/// Decodes little-endian `i64`.
+pub type I64LeDecoder = IntDecoder<i64, LittleEndian>;
+/// Decodes big-endian `i64`.
+pub type I64BeDecoder = IntDecoder<i64, BigEndian>;
+/// Decodes little-endian `i128`.
+pub type I128LeDecoder = IntDecoder<i128, LittleEndian>;
+/// Decodes big-endian `i128`.
+pub type I128BeDecoder = IntDecoder<i128, BigEndian>;
+
+#[cfg(test)]
+mod tests {
+    use super::{I16LeDecoder, U32BeDecoder, U32LeDecoder};
+    use crate::{Decoder, KnownMinLenDecoder};
+
+    #[test]
+    fn little_endian() {
+        let mut decoder = U32LeDecoder::new();
+        assert_eq!(decoder.min_required_bytes(), 4);
+        assert_eq!(decoder.bytes_received(&[1, 2]).unwrap(), 2);
+        assert_eq!(decoder.min_required_bytes(), 2);
+        assert_eq!(decoder.bytes_received(&[3, 4, 5]).unwrap(), 2);
+        assert_eq!(decoder.min_required_bytes(), 0);
+        assert_eq!(decoder.end().unwrap(), 0x04030201);
+    }
+
+    #[test]
+    fn big_endian() {
+        let mut decoder = U32BeDecoder::new();
+        assert_eq!(decoder.bytes_received(&[1, 2, 3, 4]).unwrap(), 4);
+        assert_eq!(decoder.end().unwrap(), 0x01020304);
+    }
+
+    #[test]
+    fn signed() {
+        let mut decoder = I16LeDecoder::new();
+        assert_eq!(decoder.bytes_received(&[0xFE, 0xFF]).unwrap(), 2);
+        assert_eq!(decoder.end().unwrap(), -2);
     }
 }
@@ -0,0 +1,245 @@
+use core::fmt;
+use core::marker::PhantomData;
+use crate::{Decoder, KnownMinLenDecoder};
+use crate::error::{Leb128Error, UnexpectedEnd};
+use crate::int::{Signed, Unsigned};
+
+/// Decodes an unsigned LEB128 (varint) integer.
+///
+/// Encodings that don't fit into `T` or that are longer than necessary are rejected.
+pub struct Leb128Decoder<T: Unsigned>(RawDecoder, PhantomData<fn() -> T>);
+
+impl<T: Unsigned> Leb128Decoder<T> {
+    pub fn new() -> Self {
+        Leb128Decoder(RawDecoder::new(), PhantomData)
+    }
+}
+
+impl<T: Unsigned> Default for Leb128Decoder<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Unsigned> fmt::Debug for Leb128Decoder<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Leb128Decoder<{}>({:?})", core::any::type_name::<T>(), self.0)
+    }
+}
+
+impl<T: Unsigned> Decoder for Leb128Decoder<T> {
+    type Value = T;
+    type Error = Leb128Error;
+
+    fn decode_chunk(&mut self, bytes: &mut &[u8]) -> Result<(), Self::Error> {
+        self.0.decode_chunk(bytes, T::BITS)
+    }
+
+    fn end(self) -> Result<Self::Value, Self::Error> {
+        self.0.end().map(T::from_u128)
+    }
+}
+
+impl<T: Unsigned> KnownMinLenDecoder for Leb128Decoder<T> {
+    fn min_required_bytes(&self) -> usize {
+        self.0.min_required_bytes()
+    }
+}
+
+/// Decodes a signed integer encoded as zigzag followed by unsigned LEB128.
+///
+/// This is the encoding used by protobuf `sint32`/`sint64` fields.
+pub struct ZigZagLeb128Decoder<T: Signed>(RawDecoder, PhantomData<fn() -> T>);
+
+impl<T: Signed> ZigZagLeb128Decoder<T> {
+    pub fn new() -> Self {
+        ZigZagLeb128Decoder(RawDecoder::new(), PhantomData)
+    }
+}
+
+impl<T: Signed> Default for ZigZagLeb128Decoder<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Signed> fmt::Debug for ZigZagLeb128Decoder<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ZigZagLeb128Decoder<{}>({:?})", core::any::type_name::<T>(), self.0)
+    }
+}
+
+impl<T: Signed> Decoder for ZigZagLeb128Decoder<T> {
+    type Value = T;
+    type Error = Leb128Error;
+
+    fn decode_chunk(&mut self, bytes: &mut &[u8]) -> Result<(), Self::Error> {
+        // zigzag maps signed integers to unsigned ones of the same width
+        self.0.decode_chunk(bytes, T::BITS)
+    }
+
+    fn end(self) -> Result<Self::Value, Self::Error> {
+        let value = self.0.end()?;
+        Ok(T::from_i128((value >> 1) as i128 ^ -((value & 1) as i128)))
+    }
+}
+
+impl<T: Signed> KnownMinLenDecoder for ZigZagLeb128Decoder<T> {
+    fn min_required_bytes(&self) -> usize {
+        self.0.min_required_bytes()
+    }
+}
+
+#[derive(Debug)]
+struct RawDecoder {
+    value: u128,
+    shift: u32,
+    done: bool,
+}
+
+impl RawDecoder {
+    fn new() -> Self {
+        RawDecoder {
+            value: 0,
+            shift: 0,
+            done: false,
+        }
+    }
+
+    fn decode_chunk(&mut self, bytes: &mut &[u8], bits: u32) -> Result<(), Leb128Error> {
+        while !self.done {
+            let byte = match bytes.first() {
+                Some(byte) => *byte,
+                None => break,
+            };
+            let payload = u128::from(byte & 0x7F);
+            if self.shift >= bits {
+                // all bits were already received so this byte can not carry anything useful
+                return if payload == 0 {
+                    Err(Leb128Error::OverlongEncoding)
+                } else {
+                    Err(Leb128Error::Overflow)
+                };
+            }
+            if bits - self.shift < 7 && payload >> (bits - self.shift) != 0 {
+                return Err(Leb128Error::Overflow);
+            }
+            self.value |= payload << self.shift;
+            *bytes = &bytes[1..];
+            if byte & 0x80 == 0 {
+                if payload == 0 && self.shift > 0 {
+                    return Err(Leb128Error::OverlongEncoding);
+                }
+                self.done = true;
+            } else {
+                self.shift += 7;
+            }
+        }
+        Ok(())
+    }
+
+    fn end(self) -> Result<u128, Leb128Error> {
+        if self.done {
+            Ok(self.value)
+        } else {
+            Err(Leb128Error::UnexpectedEnd(UnexpectedEnd { missing: 1 }))
+        }
+    }
+
+    fn min_required_bytes(&self) -> usize {
+        if self.done {
+            0
+        } else {
+            1
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Leb128Decoder, ZigZagLeb128Decoder};
+    use crate::Decoder;
+    use crate::error::Leb128Error;
+
+    #[test]
+    fn empty() {
+        let decoder = Leb128Decoder::<u32>::new();
+        decoder.end().unwrap_err();
+    }
+
+    #[test]
+    fn one() {
+        let mut decoder = Leb128Decoder::<u32>::new();
+        assert_eq!(decoder.bytes_received(&[42, 21]).unwrap(), 1);
+        assert_eq!(decoder.end().unwrap(), 42);
+    }
+
+    #[test]
+    fn two_split() {
+        let mut decoder = Leb128Decoder::<u32>::new();
+        assert_eq!(decoder.bytes_received(&[0xE5]).unwrap(), 1);
+        assert_eq!(decoder.bytes_received(&[0x8E, 0x26, 0x01]).unwrap(), 2);
+        assert_eq!(decoder.end().unwrap(), 624485);
+    }
+
+    #[test]
+    fn max() {
+        let mut decoder = Leb128Decoder::<u8>::new();
+        assert_eq!(decoder.bytes_received(&[0xFF, 0x01]).unwrap(), 2);
+        assert_eq!(decoder.end().unwrap(), 255);
+
+        let mut decoder = Leb128Decoder::<u128>::new();
+        let mut bytes = [0xFF; 19];
+        bytes[18] = 0x03;
+        assert_eq!(decoder.bytes_received(&bytes).unwrap(), 19);
+        assert_eq!(decoder.end().unwrap(), u128::MAX);
+    }
+
+    #[test]
+    fn overflow() {
+        let mut decoder = Leb128Decoder::<u8>::new();
+        assert!(matches!(decoder.bytes_received(&[0x80, 0x02]), Err(Leb128Error::Overflow)));
+
+        let mut decoder = Leb128Decoder::<u8>::new();
+        assert!(matches!(decoder.bytes_received(&[0x80, 0x80, 0x01]), Err(Leb128Error::Overflow)));
+    }
+
+    #[test]
+    fn overlong() {
+        let mut decoder = Leb128Decoder::<u32>::new();
+        assert!(matches!(decoder.bytes_received(&[0x81, 0x00]), Err(Leb128Error::OverlongEncoding)));
+
+        let mut decoder = Leb128Decoder::<u8>::new();
+        assert!(matches!(decoder.bytes_received(&[0x81, 0x80, 0x00]), Err(Leb128Error::OverlongEncoding)));
+    }
+
+    #[test]
+    fn zero() {
+        let mut decoder = Leb128Decoder::<u64>::new();
+        assert_eq!(decoder.bytes_received(&[0x00]).unwrap(), 1);
+        assert_eq!(decoder.end().unwrap(), 0);
+    }
+
+    #[test]
+    fn zigzag() {
+        let cases: &[(&[u8], i32)] = &[
+            (&[0x00], 0),
+            (&[0x01], -1),
+            (&[0x02], 1),
+            (&[0x03], -2),
+            (&[0xFE, 0xFF, 0xFF, 0xFF, 0x0F], i32::MAX),
+            (&[0xFF, 0xFF, 0xFF, 0xFF, 0x0F], i32::MIN),
+        ];
+        for (bytes, expected) in cases {
+            let mut decoder = ZigZagLeb128Decoder::<i32>::new();
+            assert_eq!(decoder.bytes_received(bytes).unwrap(), bytes.len());
+            assert_eq!(decoder.end().unwrap(), *expected);
+        }
+    }
+
+    #[test]
+    fn zigzag_overflow() {
+        let mut decoder = ZigZagLeb128Decoder::<i8>::new();
+        assert!(matches!(decoder.bytes_received(&[0x80, 0x02]), Err(Leb128Error::Overflow)));
+    }
+}
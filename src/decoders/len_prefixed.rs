@@ -0,0 +1,114 @@
+use core::fmt;
+use either::Either;
+use crate::{Decoder, KnownMinLenDecoder};
+use crate::decoders::Leb128Decoder;
+use crate::decoders::combinators::{Then, ThenFnPtr};
+
+/// Decoders that can be created from the length of the value.
+///
+/// The meaning of the length is defined by the implementor, e.g. the number of bytes or elements.
+/// Since the length commonly comes from an untrusted source the implementors should limit the
+/// amount of memory allocated upfront.
+pub trait WithLen: Decoder {
+    /// Creates the decoder decoding a value of length `len`.
+    fn with_len(len: usize) -> Self;
+}
+
+/// Decodes the length using `P` and then the value using `D` created with this length.
+///
+/// The length is LEB128-encoded by default. If the length doesn't fit into `usize` it's saturated
+/// which will cause the inner decoder to fail.
+pub struct LengthPrefixedDecoder<D: WithLen, P: Decoder = Leb128Decoder<u64>>(ThenFnPtr<P, D>) where P::Value: TryInto<usize>;
+
+impl<D: WithLen, P: Decoder + Default> LengthPrefixedDecoder<D, P> where P::Value: TryInto<usize> {
+    pub fn new() -> Self {
+        Self::with_prefix_decoder(P::default())
+    }
+}
+
+impl<D: WithLen, P: Decoder> LengthPrefixedDecoder<D, P> where P::Value: TryInto<usize> {
+    /// Creates the decoder using a custom length decoder.
+    pub fn with_prefix_decoder(prefix_decoder: P) -> Self {
+        fn create<D: WithLen, L: TryInto<usize>>(len: L) -> D {
+            D::with_len(len.try_into().unwrap_or(usize::MAX))
+        }
+
+        LengthPrefixedDecoder(Then::new(prefix_decoder, create::<D, P::Value> as fn(P::Value) -> D))
+    }
+}
+
+impl<D: WithLen, P: Decoder + Default> Default for LengthPrefixedDecoder<D, P> where P::Value: TryInto<usize> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<D, P> fmt::Debug for LengthPrefixedDecoder<D, P>
+where D: WithLen + fmt::Debug,
+      P: Decoder + fmt::Debug,
+      P::Value: TryInto<usize>
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("LengthPrefixedDecoder").field(&self.0).finish()
+    }
+}
+
+impl<D: WithLen, P: Decoder> Decoder for LengthPrefixedDecoder<D, P> where P::Value: TryInto<usize> {
+    type Value = D::Value;
+    type Error = Either<P::Error, D::Error>;
+
+    fn decode_chunk(&mut self, bytes: &mut &[u8]) -> Result<(), Self::Error> {
+        self.0.decode_chunk(bytes)
+    }
+
+    fn end(self) -> Result<Self::Value, Self::Error> {
+        self.0.end()
+    }
+}
+
+impl<D: WithLen + KnownMinLenDecoder, P: KnownMinLenDecoder> KnownMinLenDecoder for LengthPrefixedDecoder<D, P> where P::Value: TryInto<usize> {
+    fn min_required_bytes(&self) -> usize {
+        self.0.min_required_bytes()
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg(test)]
+mod tests {
+    use super::LengthPrefixedDecoder;
+    use crate::Decoder;
+    use crate::decoders::{ByteVecDecoder, CompactSizeDecoder, U8Decoder};
+
+    #[test]
+    fn empty() {
+        let mut decoder = LengthPrefixedDecoder::<ByteVecDecoder>::new();
+        assert_eq!(decoder.bytes_received(&[0, 42]).unwrap(), 1);
+        assert_eq!(decoder.end().unwrap(), []);
+    }
+
+    #[test]
+    fn two_split() {
+        let mut decoder = LengthPrefixedDecoder::<ByteVecDecoder>::new();
+        assert_eq!(decoder.bytes_received(&[2, 42]).unwrap(), 2);
+        assert_eq!(decoder.bytes_received(&[21, 47]).unwrap(), 1);
+        assert_eq!(decoder.end().unwrap(), [42, 21]);
+    }
+
+    #[test]
+    fn custom_prefix() {
+        let mut decoder = LengthPrefixedDecoder::<ByteVecDecoder, U8Decoder>::new();
+        assert_eq!(decoder.bytes_received(&[1, 42]).unwrap(), 2);
+        assert_eq!(decoder.end().unwrap(), [42]);
+
+        let mut decoder = LengthPrefixedDecoder::<ByteVecDecoder, _>::with_prefix_decoder(CompactSizeDecoder::new());
+        assert_eq!(decoder.bytes_received(&[1, 42]).unwrap(), 2);
+        assert_eq!(decoder.end().unwrap(), [42]);
+    }
+
+    #[test]
+    fn truncated() {
+        let mut decoder = LengthPrefixedDecoder::<ByteVecDecoder>::new();
+        assert_eq!(decoder.bytes_received(&[2, 42]).unwrap(), 2);
+        decoder.end().unwrap_err();
+    }
+}
@@ -0,0 +1,148 @@
+use core::fmt;
+use crate::{Decode, Decoder, KnownMinLenDecoder};
+use crate::error::UnexpectedEnd;
+
+/// Decodes an optional value prefixed by a tag byte.
+///
+/// The tag `0` means `None`, the tag `1` means `Some` followed by the value decoded by `D`. Other
+/// tags are rejected.
+#[derive(Debug)]
+pub struct OptionDecoder<D: Decoder + Default>(State<D>);
+
+impl<D: Decoder + Default> OptionDecoder<D> {
+    pub fn new() -> Self {
+        OptionDecoder(State::Tag)
+    }
+}
+
+impl<D: Decoder + Default> Default for OptionDecoder<D> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug)]
+enum State<D> {
+    Tag,
+    None,
+    Some(D),
+}
+
+impl<D: Decoder + Default> Decoder for OptionDecoder<D> {
+    type Value = Option<D::Value>;
+    type Error = Error<D::Error>;
+
+    fn decode_chunk(&mut self, bytes: &mut &[u8]) -> Result<(), Self::Error> {
+        if let State::Tag = self.0 {
+            let tag = match bytes.first() {
+                Some(tag) => *tag,
+                None => return Ok(()),
+            };
+            self.0 = match tag {
+                0 => State::None,
+                1 => State::Some(D::default()),
+                tag => return Err(Error::InvalidTag(tag)),
+            };
+            *bytes = &bytes[1..];
+        }
+        match &mut self.0 {
+            State::Some(decoder) => decoder.decode_chunk(bytes).map_err(Error::Value),
+            _ => Ok(()),
+        }
+    }
+
+    fn end(self) -> Result<Self::Value, Self::Error> {
+        match self.0 {
+            State::Tag => Err(Error::UnexpectedEnd(UnexpectedEnd { missing: 1 })),
+            State::None => Ok(None),
+            State::Some(decoder) => decoder.end().map(Some).map_err(Error::Value),
+        }
+    }
+}
+
+impl<D: KnownMinLenDecoder + Default> KnownMinLenDecoder for OptionDecoder<D> {
+    fn min_required_bytes(&self) -> usize {
+        match &self.0 {
+            State::Tag => 1,
+            State::None => 0,
+            State::Some(decoder) => decoder.min_required_bytes(),
+        }
+    }
+}
+
+impl<T: Decode> Decode for Option<T> {
+    type Decoder = OptionDecoder<T::Decoder>;
+}
+
+/// Error returned when decoding an optional value fails.
+#[derive(Debug, Clone)]
+pub enum Error<E> {
+    /// The stream ended before the tag was received.
+    UnexpectedEnd(UnexpectedEnd),
+    /// The tag is neither `0` nor `1`.
+    InvalidTag(u8),
+    /// Decoding the value failed.
+    Value(E),
+}
+
+impl<E> fmt::Display for Error<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::UnexpectedEnd(_) => write!(f, "unexpected end"),
+            Error::InvalidTag(tag) => write!(f, "invalid tag {} of optional value", tag),
+            Error::Value(_) => write!(f, "failed to decode the optional value"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: std::error::Error + 'static> std::error::Error for Error<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::UnexpectedEnd(error) => Some(error),
+            Error::InvalidTag(_) => None,
+            Error::Value(error) => Some(error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Error, OptionDecoder};
+    use crate::Decoder;
+    use crate::decoders::U8Decoder;
+
+    #[test]
+    fn empty() {
+        let decoder = OptionDecoder::<U8Decoder>::new();
+        decoder.end().unwrap_err();
+    }
+
+    #[test]
+    fn none() {
+        let mut decoder = OptionDecoder::<U8Decoder>::new();
+        assert_eq!(decoder.bytes_received(&[0, 42]).unwrap(), 1);
+        assert_eq!(decoder.end().unwrap(), None);
+    }
+
+    #[test]
+    fn some_split() {
+        let mut decoder = OptionDecoder::<U8Decoder>::new();
+        assert_eq!(decoder.bytes_received(&[1]).unwrap(), 1);
+        assert_eq!(decoder.bytes_received(&[42, 21]).unwrap(), 1);
+        assert_eq!(decoder.end().unwrap(), Some(42));
+    }
+
+    #[test]
+    fn some_truncated() {
+        let mut decoder = OptionDecoder::<U8Decoder>::new();
+        assert_eq!(decoder.bytes_received(&[1]).unwrap(), 1);
+        assert!(matches!(decoder.end(), Err(Error::Value(_))));
+    }
+
+    #[test]
+    fn invalid_tag() {
+        let mut decoder = OptionDecoder::<U8Decoder>::new();
+        assert!(matches!(decoder.bytes_received(&[2]), Err(Error::InvalidTag(2))));
+    }
+}
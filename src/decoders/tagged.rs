@@ -0,0 +1,286 @@
+use core::fmt;
+use crate::{Decoder, KnownMinLenDecoder};
+
+/// Decoder of one of multiple variants selected by a tag.
+///
+/// This is usually an enum with one decoder per variant, see [`tagged_decoder!`] for a
+/// convenient way to create it. It's used by [`Tagged`] to decode the value following the tag.
+///
+/// [`tagged_decoder!`]: crate::tagged_decoder
+pub trait Variants: Decoder + Sized {
+    /// The decoder of the tag.
+    ///
+    /// Its length must be known so that the variant can be selected as soon as the tag is
+    /// complete, even if it ends exactly at the end of a chunk.
+    type TagDecoder: KnownMinLenDecoder;
+
+    /// Creates the decoder of the variant identified by `tag`.
+    ///
+    /// Returns the tag back if it doesn't identify any variant.
+    fn from_tag(tag: <Self::TagDecoder as Decoder>::Value) -> Result<Self, <Self::TagDecoder as Decoder>::Value>;
+}
+
+/// Decodes a tag and then the value of the variant identified by the tag.
+pub struct Tagged<V: Variants>(State<V>);
+
+enum State<V: Variants> {
+    Tag(V::TagDecoder),
+    Variant(V),
+    Errored,
+    Panicked,
+}
+
+impl<V: Variants> Tagged<V> where V::TagDecoder: Default {
+    /// Creates the decoder using the default tag decoder.
+    pub fn new() -> Self {
+        Self::with_tag_decoder(Default::default())
+    }
+}
+
+impl<V: Variants> Tagged<V> {
+    /// Creates the decoder using a custom tag decoder.
+    pub fn with_tag_decoder(tag_decoder: V::TagDecoder) -> Self {
+        Tagged(State::Tag(tag_decoder))
+    }
+
+    fn variant(tag_decoder: V::TagDecoder) -> Result<V, Error<V>> {
+        let tag = tag_decoder.end().map_err(Error::Tag)?;
+        V::from_tag(tag).map_err(Error::UnknownTag)
+    }
+}
+
+impl<V: Variants> Default for Tagged<V> where V::TagDecoder: Default {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V> fmt::Debug for Tagged<V> where V: Variants + fmt::Debug, V::TagDecoder: fmt::Debug {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.0 {
+            State::Tag(decoder) => f.debug_tuple("Tagged::Tag").field(decoder).finish(),
+            State::Variant(decoder) => f.debug_tuple("Tagged::Variant").field(decoder).finish(),
+            State::Errored => f.debug_tuple("Tagged::Errored").finish(),
+            State::Panicked => f.debug_tuple("Tagged::Panicked").finish(),
+        }
+    }
+}
+
+impl<V: Variants> Decoder for Tagged<V> {
+    type Value = V::Value;
+    type Error = Error<V>;
+
+    fn decode_chunk(&mut self, bytes: &mut &[u8]) -> Result<(), Self::Error> {
+        match &mut self.0 {
+            State::Tag(decoder) => {
+                decoder.decode_chunk(bytes).map_err(Error::Tag)?;
+                if bytes.is_empty() && decoder.min_required_bytes() > 0 {
+                    return Ok(());
+                }
+                let decoder = match core::mem::replace(&mut self.0, State::Panicked) {
+                    State::Tag(decoder) => decoder,
+                    _ => unreachable!(),
+                };
+                match Self::variant(decoder) {
+                    Ok(variant) => self.0 = State::Variant(variant),
+                    Err(error) => {
+                        self.0 = State::Errored;
+                        return Err(error);
+                    },
+                }
+                self.decode_chunk(bytes)
+            },
+            State::Variant(decoder) => decoder.decode_chunk(bytes).map_err(Error::Variant),
+            State::Errored => panic!("use of failed decoder"),
+            State::Panicked => panic!("use of panicked decoder"),
+        }
+    }
+
+    fn end(self) -> Result<Self::Value, Self::Error> {
+        let variant = match self.0 {
+            State::Tag(decoder) => Self::variant(decoder)?,
+            State::Variant(decoder) => decoder,
+            State::Errored => panic!("use of failed decoder"),
+            State::Panicked => panic!("use of panicked decoder"),
+        };
+        variant.end().map_err(Error::Variant)
+    }
+}
+
+impl<V: Variants + KnownMinLenDecoder> KnownMinLenDecoder for Tagged<V> {
+    fn min_required_bytes(&self) -> usize {
+        match &self.0 {
+            // `decode_chunk` switches to the variant once the tag is complete so this can only
+            // be a zero-length tag that wasn't processed yet and the variant is still unknown.
+            State::Tag(decoder) => decoder.min_required_bytes().max(1),
+            State::Variant(decoder) => decoder.min_required_bytes(),
+            State::Errored => panic!("use of failed decoder"),
+            State::Panicked => panic!("use of panicked decoder"),
+        }
+    }
+}
+
+/// Error returned when decoding a tagged value fails.
+pub enum Error<V: Variants> {
+    /// Decoding the tag failed.
+    Tag(<V::TagDecoder as Decoder>::Error),
+    /// The tag doesn't identify any variant.
+    UnknownTag(<V::TagDecoder as Decoder>::Value),
+    /// Decoding the variant failed.
+    Variant(V::Error),
+}
+
+impl<V: Variants> fmt::Debug for Error<V>
+where <V::TagDecoder as Decoder>::Error: fmt::Debug,
+      <V::TagDecoder as Decoder>::Value: fmt::Debug,
+      V::Error: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Tag(error) => f.debug_tuple("Tag").field(error).finish(),
+            Error::UnknownTag(tag) => f.debug_tuple("UnknownTag").field(tag).finish(),
+            Error::Variant(error) => f.debug_tuple("Variant").field(error).finish(),
+        }
+    }
+}
+
+impl<V: Variants> fmt::Display for Error<V> where <V::TagDecoder as Decoder>::Value: fmt::Display {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Tag(_) => write!(f, "failed to decode the tag"),
+            Error::UnknownTag(tag) => write!(f, "unknown tag {}", tag),
+            Error::Variant(_) => write!(f, "failed to decode the variant"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<V: Variants> std::error::Error for Error<V>
+where <V::TagDecoder as Decoder>::Error: std::error::Error + 'static,
+      <V::TagDecoder as Decoder>::Value: fmt::Debug + fmt::Display,
+      V::Error: std::error::Error + 'static
+{
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Tag(error) => Some(error),
+            Error::UnknownTag(_) => None,
+            Error::Variant(error) => Some(error),
+        }
+    }
+}
+
+#[cfg(test)]
+// errors of some variants are never inspected
+#[allow(dead_code)]
+mod tests {
+    use super::{Error, Tagged};
+    use crate::{Decoder, KnownMinLenDecoder};
+    use crate::decoders::{ByteArrayDecoder, U8Decoder, U16BeDecoder};
+
+    #[derive(Debug, PartialEq)]
+    enum Message {
+        Ping(u16),
+        Pong { nonce: u8 },
+        Stop,
+    }
+
+    crate::tagged_decoder! {
+        #[derive(Debug)]
+        enum MessageVariants(MessageVariantsError): U8Decoder => Message {
+            0 => Ping(U16BeDecoder) => Message::Ping,
+            1 => Pong(U8Decoder) => |nonce| Message::Pong { nonce },
+            2 | 3 => Stop(ByteArrayDecoder<0>) => |_| Message::Stop,
+        }
+    }
+
+    type MessageDecoder = Tagged<MessageVariants>;
+
+    #[test]
+    fn variants() {
+        let mut decoder = MessageDecoder::new();
+        assert_eq!(decoder.bytes_received(&[0, 0, 42, 21]).unwrap(), 3);
+        assert_eq!(decoder.end().unwrap(), Message::Ping(42));
+
+        let mut decoder = MessageDecoder::new();
+        assert_eq!(decoder.bytes_received(&[1, 42]).unwrap(), 2);
+        assert_eq!(decoder.end().unwrap(), Message::Pong { nonce: 42 });
+
+        let mut decoder = MessageDecoder::new();
+        assert_eq!(decoder.bytes_received(&[3]).unwrap(), 1);
+        assert_eq!(decoder.end().unwrap(), Message::Stop);
+    }
+
+    #[test]
+    fn split() {
+        let mut decoder = MessageDecoder::new();
+        assert_eq!(decoder.min_required_bytes(), 1);
+        assert_eq!(decoder.bytes_received(&[0]).unwrap(), 1);
+        assert_eq!(decoder.min_required_bytes(), 2);
+        assert_eq!(decoder.bytes_received(&[0]).unwrap(), 1);
+        assert_eq!(decoder.min_required_bytes(), 1);
+        assert_eq!(decoder.bytes_received(&[42]).unwrap(), 1);
+        assert_eq!(decoder.min_required_bytes(), 0);
+        assert_eq!(decoder.end().unwrap(), Message::Ping(42));
+    }
+
+    #[test]
+    fn tag_alone() {
+        let mut decoder = MessageDecoder::new();
+        assert_eq!(decoder.bytes_received(&[1]).unwrap(), 1);
+        assert_eq!(decoder.min_required_bytes(), 1);
+        assert_eq!(decoder.bytes_received(&[42]).unwrap(), 1);
+        assert_eq!(decoder.end().unwrap(), Message::Pong { nonce: 42 });
+
+        let mut decoder = MessageDecoder::new();
+        assert_eq!(decoder.bytes_received(&[2]).unwrap(), 1);
+        assert_eq!(decoder.min_required_bytes(), 0);
+        assert_eq!(decoder.end().unwrap(), Message::Stop);
+    }
+
+    #[cfg(feature = "tokio-util")]
+    #[test]
+    fn codec() {
+        use bytes::BytesMut;
+        use actual_tokio_util::codec::Decoder as _;
+
+        let mut codec = crate::codec::Codec::<MessageDecoder, crate::encoders::BytesEncoder<[u8; 0]>>::new();
+        let mut buf = BytesMut::from(&[0u8][..]);
+        assert_eq!(codec.decode(&mut buf).unwrap(), None);
+        buf.extend_from_slice(&[0]);
+        assert_eq!(codec.decode(&mut buf).unwrap(), None);
+        buf.extend_from_slice(&[42, 2]);
+        assert_eq!(codec.decode(&mut buf).unwrap(), Some(Message::Ping(42)));
+        assert_eq!(codec.decode(&mut buf).unwrap(), Some(Message::Stop));
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn errors() {
+        let mut decoder = MessageDecoder::new();
+        assert!(matches!(decoder.bytes_received(&[4, 0]), Err(Error::UnknownTag(4))));
+
+        // the tag is checked as soon as it's complete
+        let mut decoder = MessageDecoder::new();
+        assert!(matches!(decoder.bytes_received(&[4]), Err(Error::UnknownTag(4))));
+
+        assert!(matches!(MessageDecoder::new().end(), Err(Error::Tag(_))));
+
+        let mut decoder = MessageDecoder::new();
+        assert_eq!(decoder.bytes_received(&[0, 0]).unwrap(), 2);
+        assert!(matches!(decoder.end(), Err(Error::Variant(MessageVariantsError::Ping(_)))));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn error_source() {
+        use alloc::string::ToString;
+        use std::error::Error as _;
+
+        let mut decoder = MessageDecoder::new();
+        assert_eq!(decoder.bytes_received(&[0, 0]).unwrap(), 2);
+        let error = decoder.end().unwrap_err();
+        let variant_error = error.source().unwrap();
+        assert_eq!(variant_error.to_string(), "failed to decode variant `Ping`");
+        assert!(variant_error.source().is_some());
+    }
+}
@@ -0,0 +1,128 @@
+use core::fmt;
+use crate::{Decode, Decoder, KnownMinLenDecoder};
+use crate::decoders::combinators::Chain;
+
+/// Decodes a tuple by decoding its elements one after another.
+///
+/// The errors are nested [`Either`](either::Either)s, the same as if the decoders were chained
+/// using [`Decoder::chain`].
+pub struct TupleDecoder<T: DecodeTuple>(T::Chain);
+
+impl<T: DecodeTuple> TupleDecoder<T> {
+    pub fn new() -> Self {
+        TupleDecoder(Default::default())
+    }
+}
+
+impl<T: DecodeTuple> Default for TupleDecoder<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: DecodeTuple> fmt::Debug for TupleDecoder<T> where T::Chain: fmt::Debug {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("TupleDecoder").field(&self.0).finish()
+    }
+}
+
+impl<T: DecodeTuple> Decoder for TupleDecoder<T> {
+    type Value = T;
+    type Error = <T::Chain as Decoder>::Error;
+
+    fn decode_chunk(&mut self, bytes: &mut &[u8]) -> Result<(), Self::Error> {
+        self.0.decode_chunk(bytes)
+    }
+
+    fn end(self) -> Result<Self::Value, Self::Error> {
+        self.0.end().map(T::from_chain)
+    }
+}
+
+impl<T: DecodeTuple> KnownMinLenDecoder for TupleDecoder<T> where T::Chain: KnownMinLenDecoder {
+    fn min_required_bytes(&self) -> usize {
+        self.0.min_required_bytes()
+    }
+}
+
+/// Tuples of values implementing [`Decode`].
+///
+/// This is an implementation detail of [`TupleDecoder`], it's sealed and you shouldn't need to
+/// use it directly.
+pub trait DecodeTuple: Sized + sealed::DecodeTuple {
+    #[doc(hidden)]
+    type Chain: Decoder + Default;
+
+    #[doc(hidden)]
+    fn from_chain(value: <Self::Chain as Decoder>::Value) -> Self;
+}
+
+mod sealed {
+    pub trait DecodeTuple {}
+}
+
+macro_rules! chain_type {
+    ($last:ident) => { $last::Decoder };
+    ($first:ident, $($rest:ident),+) => { Chain<$first::Decoder, chain_type!($($rest),+)> };
+}
+
+macro_rules! chain_pattern {
+    ($last:ident) => { $last };
+    ($first:ident, $($rest:ident),+) => { ($first, chain_pattern!($($rest),+)) };
+}
+
+macro_rules! impl_tuple {
+    ($($param:ident),+) => {
+        impl<$($param: Decode),+> DecodeTuple for ($($param,)+) {
+            type Chain = chain_type!($($param),+);
+
+            #[allow(non_snake_case)]
+            fn from_chain(value: <Self::Chain as Decoder>::Value) -> Self {
+                let chain_pattern!($($param),+) = value;
+                ($($param,)+)
+            }
+        }
+
+        impl<$($param: Decode),+> sealed::DecodeTuple for ($($param,)+) {}
+
+        impl<$($param: Decode),+> Decode for ($($param,)+) {
+            type Decoder = TupleDecoder<Self>;
+        }
+    }
+}
+
+impl_tuple!(A, B);
+impl_tuple!(A, B, C);
+impl_tuple!(A, B, C, D);
+impl_tuple!(A, B, C, D, E);
+impl_tuple!(A, B, C, D, E, F);
+impl_tuple!(A, B, C, D, E, F, G);
+impl_tuple!(A, B, C, D, E, F, G, H);
+
+#[cfg(test)]
+mod tests {
+    use super::TupleDecoder;
+    use crate::Decoder;
+
+    #[test]
+    fn pair() {
+        let mut decoder = TupleDecoder::<(u8, u16)>::new();
+        assert_eq!(decoder.bytes_received(&[1, 0]).unwrap(), 2);
+        assert_eq!(decoder.bytes_received(&[2, 42]).unwrap(), 1);
+        assert_eq!(decoder.end().unwrap(), (1, 2));
+    }
+
+    #[test]
+    fn triple() {
+        let mut decoder = TupleDecoder::<(u8, [u8; 2], u8)>::new();
+        assert_eq!(decoder.bytes_received(&[1, 2, 3, 4, 5]).unwrap(), 4);
+        assert_eq!(decoder.end().unwrap(), (1, [2, 3], 4));
+    }
+
+    #[test]
+    fn truncated() {
+        let mut decoder = TupleDecoder::<(u8, u8, u8)>::new();
+        assert_eq!(decoder.bytes_received(&[1, 2]).unwrap(), 2);
+        decoder.end().unwrap_err();
+    }
+}
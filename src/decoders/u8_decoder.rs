@@ -19,7 +19,7 @@ impl Decoder for U8Decoder {
     type Error = UnexpectedEnd;
 
     fn decode_chunk(&mut self, bytes: &mut &[u8]) -> Result<(), Self::Error> {
-        match (self.buf, bytes.get(0)) {
+        match (self.buf, bytes.first()) {
             (None, Some(byte)) => {
                 self.buf = Some(*byte);
                 *bytes = &bytes[1..];
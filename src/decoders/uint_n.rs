@@ -0,0 +1,102 @@
+use core::fmt;
+use core::marker::PhantomData;
+use crate::{Decoder, KnownMinLenDecoder};
+use crate::decoders::ByteArrayDecoder;
+use crate::error::UnexpectedEnd;
+use crate::int::*;
+
+/// Decodes an `N`-byte unsigned integer into a (wider) integer `T`.
+///
+/// This is useful for formats using integers of unusual sizes, e.g. 24-bit lengths in TLS
+/// handshake messages: `UintNDecoder<3, u32, BigEndian>`.
+///
+/// `N` must not be larger than the size of `T`, this is checked at compile time:
+///
+/// ```compile_fail
+/// use push_decode::decoders::UintNDecoder;
+/// use push_decode::int::BigEndian;
+///
+/// let decoder = UintNDecoder::<9, u64, BigEndian>::new();
+/// ```
+pub struct UintNDecoder<const N: usize, T: Unsigned, Endian: ByteOrder>(ByteArrayDecoder<N>, PhantomData<fn() -> T>, PhantomData<Endian>);
+
+impl<const N: usize, T: Unsigned, Endian: ByteOrder> UintNDecoder<N, T, Endian> {
+    // Overflows if `N` doesn't fit into `T` which fails the compilation when it's used.
+    // (`assert!` is not available in constants in our MSRV.)
+    const CHECK_SIZE: usize = core::mem::size_of::<T>() - N;
+
+    /// Creates the decoder.
+    pub fn new() -> Self {
+        let _ = Self::CHECK_SIZE;
+        UintNDecoder(ByteArrayDecoder::new(), PhantomData, PhantomData)
+    }
+}
+
+impl<const N: usize, T: Unsigned, Endian: ByteOrder> Default for UintNDecoder<N, T, Endian> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize, T: Unsigned, Endian: ByteOrder> fmt::Debug for UintNDecoder<N, T, Endian> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "UintNDecoder<{}, {}, {}>({:?})", N, core::any::type_name::<T>(), core::any::type_name::<Endian>(), self.0)
+    }
+}
+
+impl<const N: usize, T: Unsigned, Endian: ByteOrder> Decoder for UintNDecoder<N, T, Endian> {
+    type Value = T;
+    type Error = UnexpectedEnd;
+
+    fn decode_chunk(&mut self, bytes: &mut &[u8]) -> Result<(), Self::Error> {
+        self.0.decode_chunk(bytes)
+    }
+
+    fn end(self) -> Result<Self::Value, Self::Error> {
+        // fits because `new` checks the size
+        self.0.end().map(|bytes| T::from_u128(Endian::uint_from_bytes(&bytes)))
+    }
+}
+
+impl<const N: usize, T: Unsigned, Endian: ByteOrder> KnownMinLenDecoder for UintNDecoder<N, T, Endian> {
+    fn min_required_bytes(&self) -> usize {
+        self.0.min_required_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::UintNDecoder;
+    use crate::{Decoder, KnownMinLenDecoder};
+    use crate::int::{BigEndian, LittleEndian};
+
+    #[test]
+    fn u24_be() {
+        let mut decoder = UintNDecoder::<3, u32, BigEndian>::new();
+        assert_eq!(decoder.bytes_received(&[1]).unwrap(), 1);
+        assert_eq!(decoder.min_required_bytes(), 2);
+        assert_eq!(decoder.bytes_received(&[2, 3, 4]).unwrap(), 2);
+        assert_eq!(decoder.end().unwrap(), 0x010203);
+    }
+
+    #[test]
+    fn u48_le() {
+        let mut decoder = UintNDecoder::<6, u64, LittleEndian>::new();
+        assert_eq!(decoder.bytes_received(&[1, 2, 3, 4, 5, 0xFF]).unwrap(), 6);
+        assert_eq!(decoder.end().unwrap(), 0xFF0504030201);
+    }
+
+    #[test]
+    fn truncated() {
+        let mut decoder = UintNDecoder::<3, u32, BigEndian>::new();
+        assert_eq!(decoder.bytes_received(&[1]).unwrap(), 1);
+        assert_eq!(decoder.end().unwrap_err().missing, 2);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn debug() {
+        let decoder = UintNDecoder::<3, u32, BigEndian>::new();
+        assert!(alloc::format!("{:?}", decoder).starts_with("UintNDecoder<3, u32, "));
+    }
+}
@@ -0,0 +1,318 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use crate::{Decoder, KnownMinLenDecoder};
+use crate::error::UnexpectedEnd;
+use super::utf8_string::{into_string, validate_tail};
+
+/// Decodes bytes up to a delimiter, e.g. null-terminated strings or lines.
+///
+/// The delimiter is consumed but not included in the value unless
+/// [`include_delimiter`](Self::include_delimiter) is used. Since the length is unknown upfront,
+/// the number of bytes preceding the delimiter is limited to `max_len` to avoid exhausting memory.
+#[derive(Debug)]
+pub struct UntilByteDecoder {
+    buf: Vec<u8>,
+    delimiter: u8,
+    max_len: usize,
+    include_delimiter: bool,
+    trim_cr: bool,
+    done: bool,
+}
+
+impl UntilByteDecoder {
+    /// Creates the decoder decoding up to `delimiter`.
+    pub fn new(delimiter: u8, max_len: usize) -> Self {
+        UntilByteDecoder {
+            buf: Vec::new(),
+            delimiter,
+            max_len,
+            include_delimiter: false,
+            trim_cr: false,
+            done: false,
+        }
+    }
+
+    /// Creates the decoder decoding a line terminated by either `\n` or `\r\n`.
+    ///
+    /// If the delimiter is excluded the trailing `\r` is removed as well.
+    pub fn new_line(max_len: usize) -> Self {
+        UntilByteDecoder {
+            trim_cr: true,
+            ..Self::new(b'\n', max_len)
+        }
+    }
+
+    /// Sets whether the delimiter should be included at the end of the value.
+    pub fn include_delimiter(mut self, include: bool) -> Self {
+        self.include_delimiter = include;
+        self
+    }
+}
+
+impl Decoder for UntilByteDecoder {
+    type Value = Vec<u8>;
+    type Error = Error;
+
+    fn decode_chunk(&mut self, bytes: &mut &[u8]) -> Result<(), Self::Error> {
+        if self.done {
+            return Ok(());
+        }
+        let allowed = self.max_len - self.buf.len();
+        // looking at one more byte which may be the delimiter
+        let to_search = &bytes[..bytes.len().min(allowed.saturating_add(1))];
+        match to_search.iter().position(|byte| *byte == self.delimiter) {
+            Some(pos) => {
+                self.buf.extend_from_slice(&bytes[..pos]);
+                *bytes = &bytes[(pos + 1)..];
+                self.done = true;
+                if self.include_delimiter {
+                    self.buf.push(self.delimiter);
+                } else if self.trim_cr && self.buf.last() == Some(&b'\r') {
+                    self.buf.pop();
+                }
+                Ok(())
+            },
+            None if to_search.len() > allowed => Err(Error::TooLong { max_len: self.max_len }),
+            None => {
+                self.buf.extend_from_slice(to_search);
+                *bytes = &bytes[to_search.len()..];
+                Ok(())
+            },
+        }
+    }
+
+    fn end(self) -> Result<Self::Value, Self::Error> {
+        if self.done {
+            Ok(self.buf)
+        } else {
+            Err(Error::UnexpectedEnd(UnexpectedEnd { missing: 1 }))
+        }
+    }
+}
+
+impl KnownMinLenDecoder for UntilByteDecoder {
+    fn min_required_bytes(&self) -> usize {
+        if self.done {
+            0
+        } else {
+            1
+        }
+    }
+}
+
+/// Decodes UTF-8 string up to a delimiter.
+///
+/// This is the same as [`UntilByteDecoder`] but it also validates the bytes as they arrive.
+#[derive(Debug)]
+pub struct Utf8UntilByteDecoder {
+    // invariant: inner.buf[..valid_up_to] is a valid string
+    inner: UntilByteDecoder,
+    valid_up_to: usize,
+}
+
+impl Utf8UntilByteDecoder {
+    /// Creates the decoder decoding up to `delimiter`.
+    ///
+    /// # Panics
+    ///
+    /// If the `delimiter` is not ASCII since it could be a part of a multi-byte character.
+    pub fn new(delimiter: u8, max_len: usize) -> Self {
+        assert!(delimiter.is_ascii(), "the delimiter {:#04x} is not ASCII", delimiter);
+        Utf8UntilByteDecoder {
+            inner: UntilByteDecoder::new(delimiter, max_len),
+            valid_up_to: 0,
+        }
+    }
+
+    /// Creates the decoder decoding a line terminated by either `\n` or `\r\n`.
+    ///
+    /// If the delimiter is excluded the trailing `\r` is removed as well.
+    pub fn new_line(max_len: usize) -> Self {
+        Utf8UntilByteDecoder {
+            inner: UntilByteDecoder::new_line(max_len),
+            valid_up_to: 0,
+        }
+    }
+
+    /// Sets whether the delimiter should be included at the end of the value.
+    pub fn include_delimiter(self, include: bool) -> Self {
+        Utf8UntilByteDecoder {
+            inner: self.inner.include_delimiter(include),
+            valid_up_to: self.valid_up_to,
+        }
+    }
+}
+
+impl Decoder for Utf8UntilByteDecoder {
+    type Value = String;
+    type Error = Utf8Error;
+
+    fn decode_chunk(&mut self, bytes: &mut &[u8]) -> Result<(), Self::Error> {
+        self.inner.decode_chunk(bytes).map_err(Utf8Error::from)?;
+        // `\r` might have been trimmed
+        self.valid_up_to = self.valid_up_to.min(self.inner.buf.len());
+        validate_tail(&self.inner.buf, &mut self.valid_up_to).map_err(Utf8Error::InvalidUtf8)
+    }
+
+    fn end(self) -> Result<Self::Value, Self::Error> {
+        let valid_up_to = self.valid_up_to;
+        let buf = self.inner.end()?;
+        into_string(buf, valid_up_to).map_err(Utf8Error::InvalidUtf8)
+    }
+}
+
+impl KnownMinLenDecoder for Utf8UntilByteDecoder {
+    fn min_required_bytes(&self) -> usize {
+        self.inner.min_required_bytes()
+    }
+}
+
+/// Error returned when decoding bytes up to a delimiter fails.
+#[derive(Debug, Clone)]
+pub enum Error {
+    /// The stream ended before the delimiter was found.
+    UnexpectedEnd(UnexpectedEnd),
+    /// The delimiter wasn't found within the maximum length.
+    TooLong {
+        /// The maximum allowed length.
+        max_len: usize,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::UnexpectedEnd(_) => write!(f, "unexpected end"),
+            Error::TooLong { max_len } => write!(f, "the delimiter was not found within {} bytes", max_len),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::UnexpectedEnd(error) => Some(error),
+            Error::TooLong { .. } => None,
+        }
+    }
+}
+
+/// Error returned when decoding UTF-8 string up to a delimiter fails.
+#[derive(Debug, Clone)]
+pub enum Utf8Error {
+    /// The stream ended before the delimiter was found.
+    UnexpectedEnd(UnexpectedEnd),
+    /// The delimiter wasn't found within the maximum length.
+    TooLong {
+        /// The maximum allowed length.
+        max_len: usize,
+    },
+    /// The bytes are not valid UTF-8.
+    InvalidUtf8(core::str::Utf8Error),
+}
+
+impl From<Error> for Utf8Error {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::UnexpectedEnd(error) => Utf8Error::UnexpectedEnd(error),
+            Error::TooLong { max_len } => Utf8Error::TooLong { max_len },
+        }
+    }
+}
+
+impl fmt::Display for Utf8Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Utf8Error::UnexpectedEnd(_) => write!(f, "unexpected end"),
+            Utf8Error::TooLong { max_len } => write!(f, "the delimiter was not found within {} bytes", max_len),
+            Utf8Error::InvalidUtf8(_) => write!(f, "supplied bytes are not valid UTF-8"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Utf8Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Utf8Error::UnexpectedEnd(error) => Some(error),
+            Utf8Error::TooLong { .. } => None,
+            Utf8Error::InvalidUtf8(error) => Some(error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Error, UntilByteDecoder, Utf8Error, Utf8UntilByteDecoder};
+    use crate::{Decoder, KnownMinLenDecoder};
+
+    #[test]
+    fn c_string() {
+        let mut decoder = UntilByteDecoder::new(0, 16);
+        assert_eq!(decoder.bytes_received(b"foo").unwrap(), 3);
+        assert_eq!(decoder.min_required_bytes(), 1);
+        assert_eq!(decoder.bytes_received(b"bar\0baz").unwrap(), 4);
+        assert_eq!(decoder.min_required_bytes(), 0);
+        assert_eq!(decoder.bytes_received(b"baz").unwrap(), 0);
+        assert_eq!(decoder.end().unwrap(), b"foobar");
+    }
+
+    #[test]
+    fn include_delimiter() {
+        let mut decoder = UntilByteDecoder::new(b'\n', 16).include_delimiter(true);
+        assert_eq!(decoder.bytes_received(b"foo\nbar").unwrap(), 4);
+        assert_eq!(decoder.end().unwrap(), b"foo\n");
+    }
+
+    #[test]
+    fn lines() {
+        for input in [&b"foo\n"[..], b"foo\r\n"] {
+            let mut decoder = UntilByteDecoder::new_line(16);
+            assert_eq!(decoder.bytes_received(input).unwrap(), input.len());
+            assert_eq!(decoder.end().unwrap(), b"foo");
+
+            let mut decoder = UntilByteDecoder::new_line(16).include_delimiter(true);
+            assert_eq!(decoder.bytes_received(input).unwrap(), input.len());
+            assert_eq!(decoder.end().unwrap(), input);
+        }
+    }
+
+    #[test]
+    fn max_len() {
+        let mut decoder = UntilByteDecoder::new(0, 3);
+        assert_eq!(decoder.bytes_received(b"foo\0").unwrap(), 4);
+        assert_eq!(decoder.end().unwrap(), b"foo");
+
+        let mut decoder = UntilByteDecoder::new(0, 3);
+        assert_eq!(decoder.bytes_received(b"fo").unwrap(), 2);
+        assert!(matches!(decoder.bytes_received(b"ob"), Err(Error::TooLong { max_len: 3 })));
+    }
+
+    #[test]
+    fn unexpected_end() {
+        let mut decoder = UntilByteDecoder::new(0, 16);
+        assert_eq!(decoder.bytes_received(b"foo").unwrap(), 3);
+        assert!(matches!(decoder.end(), Err(Error::UnexpectedEnd(_))));
+    }
+
+    #[test]
+    fn utf8_split() {
+        let mut decoder = Utf8UntilByteDecoder::new_line(16);
+        assert_eq!(decoder.bytes_received(&[0xF0, 0x9F]).unwrap(), 2);
+        assert_eq!(decoder.bytes_received(&[0xA6, 0x80, b'\r']).unwrap(), 3);
+        assert_eq!(decoder.bytes_received(b"\nfoo").unwrap(), 1);
+        assert_eq!(decoder.end().unwrap(), "🦀");
+    }
+
+    #[test]
+    fn utf8_invalid() {
+        let mut decoder = Utf8UntilByteDecoder::new(0, 16);
+        assert!(matches!(decoder.bytes_received(&[b'a', 0xFF]), Err(Utf8Error::InvalidUtf8(_))));
+
+        let mut decoder = Utf8UntilByteDecoder::new(0, 16);
+        assert_eq!(decoder.bytes_received(&[0xF0, 0x9F, 0]).unwrap(), 3);
+        assert!(matches!(decoder.end(), Err(Utf8Error::InvalidUtf8(_))));
+    }
+}
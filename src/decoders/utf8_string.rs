@@ -1,7 +1,8 @@
 use alloc::string::String;
 use alloc::vec::Vec;
 use core::fmt;
-use crate::{Decoder, KnownMinLenDecoder};
+use crate::{Decode, Decoder, KnownMinLenDecoder};
+use crate::decoders::{LengthPrefixedDecoder, WithLen};
 use crate::error::UnexpectedEnd;
 
 #[derive(Debug)]
@@ -10,6 +11,7 @@ pub struct Utf8StringDecoder {
     buf: Vec<u8>,
     valid_up_to: usize,
     required: usize,
+    reserve_limit: usize,
 }
 
 impl Utf8StringDecoder {
@@ -18,6 +20,16 @@ impl Utf8StringDecoder {
             buf: Vec::new(),
             valid_up_to: 0,
             required: len_bytes,
+            reserve_limit: usize::MAX,
+        }
+    }
+
+    pub fn with_reserve_limit(len_bytes: usize, limit: usize) -> Self {
+        Utf8StringDecoder {
+            buf: Vec::new(),
+            valid_up_to: 0,
+            required: len_bytes,
+            reserve_limit: limit,
         }
     }
 }
@@ -32,7 +44,7 @@ impl Decoder for Utf8StringDecoder {
             return Ok(());
         }
         if self.buf.capacity() == 0 {
-            self.buf.reserve(self.required);
+            self.buf.reserve(self.required.min(self.reserve_limit));
         }
         // pre-check to avoid copying if the bytes are invalid anyway
         if self.valid_up_to == self.buf.len() {
@@ -53,19 +65,9 @@ impl Decoder for Utf8StringDecoder {
             }
         } else {
             self.buf.extend_from_slice(&bytes[..to_copy]);
-            match core::str::from_utf8(&self.buf[self.valid_up_to..]) {
-                Ok(_) => {
-                    *bytes = &bytes[to_copy..];
-                    self.valid_up_to = self.buf.len();
-                    Ok(())
-                },
-                Err(error) if error.error_len().is_none() => {
-                    *bytes = &bytes[to_copy..];
-                    self.valid_up_to += error.valid_up_to();
-                    Ok(())
-                },
-                Err(error) => Err(Error::InvalidUtf8(error)),
-            }
+            validate_tail(&self.buf, &mut self.valid_up_to).map_err(Error::InvalidUtf8)?;
+            *bytes = &bytes[to_copy..];
+            Ok(())
         }
     }
 
@@ -73,23 +75,56 @@ impl Decoder for Utf8StringDecoder {
         if self.buf.len() < self.required {
             Err(Error::UnexpectedEnd(UnexpectedEnd { missing: self.required - self.buf.len() }))
         } else {
-            if self.valid_up_to == self.buf.len() {
-                // SAFETY: guaranteed by invariant and the check above
-                Ok(unsafe { String::from_utf8_unchecked(self.buf) })
-            } else {
-                // Unfortunately we have to re-validate to produce the error. :(
-                Err(Error::InvalidUtf8(core::str::from_utf8(&self.buf).unwrap_err()))
-            }
+            into_string(self.buf, self.valid_up_to).map_err(Error::InvalidUtf8)
         }
     }
 }
 
+/// Validates the bytes of `buf` following `valid_up_to` and moves `valid_up_to` past them.
+///
+/// An incomplete sequence at the end of `buf` is not an error since the remaining bytes may arrive
+/// later.
+pub(super) fn validate_tail(buf: &[u8], valid_up_to: &mut usize) -> Result<(), core::str::Utf8Error> {
+    match core::str::from_utf8(&buf[*valid_up_to..]) {
+        Ok(_) => {
+            *valid_up_to = buf.len();
+            Ok(())
+        },
+        Err(error) if error.error_len().is_none() => {
+            *valid_up_to += error.valid_up_to();
+            Ok(())
+        },
+        Err(error) => Err(error),
+    }
+}
+
+/// Converts the buffer validated using `validate_tail` into `String`.
+pub(super) fn into_string(buf: Vec<u8>, valid_up_to: usize) -> Result<String, core::str::Utf8Error> {
+    if valid_up_to == buf.len() {
+        // SAFETY: `validate_tail` checked the bytes up to `valid_up_to`
+        Ok(unsafe { String::from_utf8_unchecked(buf) })
+    } else {
+        // Unfortunately we have to re-validate to produce the error. :(
+        Err(core::str::from_utf8(&buf).unwrap_err())
+    }
+}
+
 impl KnownMinLenDecoder for Utf8StringDecoder {
     fn min_required_bytes(&self) -> usize {
         self.required - self.buf.len()
     }
 }
 
+impl WithLen for Utf8StringDecoder {
+    fn with_len(len: usize) -> Self {
+        Self::with_reserve_limit(len, super::RESERVE_LIMIT)
+    }
+}
+
+impl Decode for String {
+    type Decoder = LengthPrefixedDecoder<Utf8StringDecoder>;
+}
+
 #[derive(Debug)]
 pub enum Error {
     InvalidUtf8(core::str::Utf8Error),
@@ -0,0 +1,210 @@
+use alloc::vec::Vec;
+use core::fmt;
+use crate::{Decode, Decoder, KnownMinLenDecoder};
+use crate::decoders::{LengthPrefixedDecoder, WithLen};
+
+/// Decodes a known number of elements into a `Vec`.
+///
+/// Each element is decoded by a fresh instance of `D` created using `Default`.
+pub struct VecDecoder<D: Decoder + Default> {
+    items: Vec<D::Value>,
+    current: D,
+    required: usize,
+}
+
+impl<D: Decoder + Default> VecDecoder<D> {
+    pub fn new(required_items: usize) -> Self {
+        VecDecoder {
+            items: Vec::with_capacity(required_items),
+            current: D::default(),
+            required: required_items,
+        }
+    }
+
+    /// Creates the decoder limiting the number of items initially reserved.
+    ///
+    /// This is useful to avoid allocating huge amount of memory if the number of items comes from
+    /// an untrusted source.
+    pub fn with_reserve_limit(required_items: usize, limit: usize) -> Self {
+        VecDecoder {
+            items: Vec::with_capacity(required_items.min(limit)),
+            current: D::default(),
+            required: required_items,
+        }
+    }
+}
+
+impl<D> fmt::Debug for VecDecoder<D>
+where D: Decoder + Default + fmt::Debug,
+      D::Value: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("VecDecoder")
+            .field("items", &self.items)
+            .field("current", &self.current)
+            .field("required", &self.required)
+            .finish()
+    }
+}
+
+impl<D: Decoder + Default> Decoder for VecDecoder<D> {
+    type Value = Vec<D::Value>;
+    type Error = Error<D::Error>;
+
+    fn decode_chunk(&mut self, bytes: &mut &[u8]) -> Result<(), Self::Error> {
+        while self.items.len() < self.required {
+            let index = self.items.len();
+            self.current.decode_chunk(bytes).map_err(|error| Error { index, error })?;
+            if bytes.is_empty() {
+                break;
+            }
+            let item = self.current.take().map_err(|error| Error { index, error })?;
+            self.items.push(item);
+        }
+        Ok(())
+    }
+
+    fn end(mut self) -> Result<Self::Value, Self::Error> {
+        if self.items.len() < self.required {
+            let index = self.items.len();
+            let item = self.current.end().map_err(|error| Error { index, error })?;
+            self.items.push(item);
+        }
+        // Allows zero-length elements, for others this returns the error
+        while self.items.len() < self.required {
+            let index = self.items.len();
+            let item = D::default().end().map_err(|error| Error { index, error })?;
+            self.items.push(item);
+        }
+        Ok(self.items)
+    }
+}
+
+impl<D: KnownMinLenDecoder + Default> KnownMinLenDecoder for VecDecoder<D> {
+    fn min_required_bytes(&self) -> usize {
+        if self.items.len() == self.required {
+            return 0;
+        }
+        match self.current.min_required_bytes() {
+            // the current element is complete but it was not taken yet
+            0 if self.required - self.items.len() > 1 => D::default().min_required_bytes(),
+            min => min,
+        }
+    }
+}
+
+impl<D: Decoder + Default> WithLen for VecDecoder<D> {
+    fn with_len(len: usize) -> Self {
+        let limit = super::RESERVE_LIMIT / core::mem::size_of::<D::Value>().max(1);
+        Self::with_reserve_limit(len, limit)
+    }
+}
+
+impl<T: Decode> Decode for Vec<T> {
+    type Decoder = LengthPrefixedDecoder<VecDecoder<T::Decoder>>;
+}
+
+/// Error returned when decoding an element of the `Vec` fails.
+#[derive(Debug, Clone)]
+pub struct Error<E> {
+    index: usize,
+    error: E,
+}
+
+impl<E> Error<E> {
+    /// Returns the index of the element that failed to decode.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the error returned by the element decoder.
+    pub fn inner(&self) -> &E {
+        &self.error
+    }
+
+    /// Returns the error returned by the element decoder.
+    pub fn into_inner(self) -> E {
+        self.error
+    }
+}
+
+impl<E> fmt::Display for Error<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "failed to decode element at index {}", self.index)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: std::error::Error + 'static> std::error::Error for Error<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::VecDecoder;
+    use crate::{Decoder, KnownMinLenDecoder};
+    use crate::decoders::IntDecoder;
+    use crate::int::BigEndian;
+
+    type U16Decoder = IntDecoder<u16, BigEndian>;
+
+    #[test]
+    fn empty() {
+        let mut decoder = VecDecoder::<U16Decoder>::new(0);
+        assert_eq!(decoder.bytes_received(&[42]).unwrap(), 0);
+        assert_eq!(decoder.end().unwrap(), []);
+    }
+
+    #[test]
+    fn empty_immediate_end() {
+        let decoder = VecDecoder::<U16Decoder>::new(0);
+        assert_eq!(decoder.end().unwrap(), []);
+    }
+
+    #[test]
+    fn two_single() {
+        let mut decoder = VecDecoder::<U16Decoder>::new(2);
+        assert_eq!(decoder.bytes_received(&[0, 1, 0, 2]).unwrap(), 4);
+        assert_eq!(decoder.end().unwrap(), [1, 2]);
+    }
+
+    #[test]
+    fn two_split() {
+        let mut decoder = VecDecoder::<U16Decoder>::new(2);
+        assert_eq!(decoder.bytes_received(&[0]).unwrap(), 1);
+        assert_eq!(decoder.min_required_bytes(), 1);
+        assert_eq!(decoder.bytes_received(&[1, 0]).unwrap(), 2);
+        assert_eq!(decoder.min_required_bytes(), 1);
+        assert_eq!(decoder.bytes_received(&[2, 42]).unwrap(), 1);
+        assert_eq!(decoder.min_required_bytes(), 0);
+        assert_eq!(decoder.end().unwrap(), [1, 2]);
+    }
+
+    #[test]
+    fn boundary_split() {
+        let mut decoder = VecDecoder::<U16Decoder>::new(2);
+        assert_eq!(decoder.bytes_received(&[0, 1]).unwrap(), 2);
+        assert_eq!(decoder.min_required_bytes(), 2);
+        assert_eq!(decoder.bytes_received(&[0, 2]).unwrap(), 2);
+        assert_eq!(decoder.end().unwrap(), [1, 2]);
+    }
+
+    #[test]
+    fn reserve_limit() {
+        let mut decoder = VecDecoder::<U16Decoder>::with_reserve_limit(usize::MAX, 1);
+        assert_eq!(decoder.bytes_received(&[0, 1]).unwrap(), 2);
+    }
+
+    #[test]
+    fn error_index() {
+        let mut decoder = VecDecoder::<U16Decoder>::new(3);
+        assert_eq!(decoder.bytes_received(&[0, 1, 0]).unwrap(), 3);
+        assert_eq!(decoder.end().unwrap_err().index(), 1);
+
+        let mut decoder = VecDecoder::<U16Decoder>::new(3);
+        assert_eq!(decoder.bytes_received(&[0, 1, 0, 2]).unwrap(), 4);
+        assert_eq!(decoder.end().unwrap_err().index(), 2);
+    }
+}
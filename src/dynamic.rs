@@ -0,0 +1,159 @@
+//! Object-safe versions of [`Decoder`] and [`Encoder`].
+
+use alloc::boxed::Box;
+use crate::{Decoder, Encoder};
+
+/// Object-safe version of [`Decoder`].
+///
+/// This is implemented for all decoders and allows choosing the decoder at runtime, e.g. in
+/// plugin-style protocol handlers. `Box<dyn DynDecoder<Value, Error>>` implements [`Decoder`] so
+/// it can be used with all the functions accepting decoders.
+///
+/// The methods have distinct names to avoid ambiguity when both traits are in scope.
+pub trait DynDecoder<Value, Error> {
+    /// Same as [`Decoder::decode_chunk`].
+    fn decode_chunk_dyn(&mut self, bytes: &mut &[u8]) -> Result<(), Error>;
+
+    /// Same as [`Decoder::end`] but operates on a boxed decoder.
+    fn end_boxed(self: Box<Self>) -> Result<Value, Error>;
+}
+
+impl<D: Decoder> DynDecoder<D::Value, D::Error> for D {
+    fn decode_chunk_dyn(&mut self, bytes: &mut &[u8]) -> Result<(), D::Error> {
+        self.decode_chunk(bytes)
+    }
+
+    fn end_boxed(self: Box<Self>) -> Result<D::Value, D::Error> {
+        (*self).end()
+    }
+}
+
+macro_rules! impl_decoder_for_dyn {
+    ($($bounds:tt)*) => {
+        impl<'a, Value, Error> Decoder for Box<dyn DynDecoder<Value, Error> $($bounds)* + 'a> {
+            type Value = Value;
+            type Error = Error;
+
+            fn decode_chunk(&mut self, bytes: &mut &[u8]) -> Result<(), Self::Error> {
+                (**self).decode_chunk_dyn(bytes)
+            }
+
+            fn end(self) -> Result<Self::Value, Self::Error> {
+                self.end_boxed()
+            }
+        }
+    }
+}
+
+impl_decoder_for_dyn!();
+impl_decoder_for_dyn!(+ Send);
+
+/// Object-safe version of [`Encoder`].
+///
+/// This is implemented for all encoders and `Box<dyn DynEncoder>` implements [`Encoder`].
+///
+/// The methods have distinct names to avoid ambiguity when both traits are in scope.
+pub trait DynEncoder {
+    /// Same as [`Encoder::encoded_chunk`].
+    fn encoded_chunk_dyn(&self) -> &[u8];
+
+    /// Same as [`Encoder::next`].
+    fn next_dyn(&mut self) -> bool;
+
+    /// Same as [`Encoder::encoded_len_hint`].
+    fn encoded_len_hint_dyn(&self) -> Option<usize>;
+
+    /// Same as [`Encoder::lookahead`].
+    fn lookahead_dyn<'a>(&'a self, chunks: &mut [&'a [u8]]) -> (usize, bool);
+}
+
+impl<E: Encoder> DynEncoder for E {
+    fn encoded_chunk_dyn(&self) -> &[u8] {
+        self.encoded_chunk()
+    }
+
+    fn next_dyn(&mut self) -> bool {
+        self.next()
+    }
+
+    fn encoded_len_hint_dyn(&self) -> Option<usize> {
+        self.encoded_len_hint()
+    }
+
+    fn lookahead_dyn<'a>(&'a self, chunks: &mut [&'a [u8]]) -> (usize, bool) {
+        self.lookahead(chunks)
+    }
+}
+
+macro_rules! impl_encoder_for_dyn {
+    ($($bounds:tt)*) => {
+        impl<'a> Encoder for Box<dyn DynEncoder $($bounds)* + 'a> {
+            fn encoded_chunk(&self) -> &[u8] {
+                (**self).encoded_chunk_dyn()
+            }
+
+            fn next(&mut self) -> bool {
+                (**self).next_dyn()
+            }
+
+            fn encoded_len_hint(&self) -> Option<usize> {
+                (**self).encoded_len_hint_dyn()
+            }
+
+            fn lookahead<'b>(&'b self, chunks: &mut [&'b [u8]]) -> (usize, bool) {
+                (**self).lookahead_dyn(chunks)
+            }
+        }
+    }
+}
+
+impl_encoder_for_dyn!();
+impl_encoder_for_dyn!(+ Send);
+
+#[cfg(test)]
+mod tests {
+    use alloc::boxed::Box;
+    use alloc::vec::Vec;
+    use super::{DynDecoder, DynEncoder};
+    use crate::{Decoder, Encoder};
+    use crate::decoders::{U8Decoder, U16BeDecoder};
+    use crate::encoders::IntEncoder;
+    use crate::error::UnexpectedEnd;
+
+    fn handler(kind: u8) -> Box<dyn DynDecoder<u16, UnexpectedEnd> + Send> {
+        match kind {
+            0 => Box::new(U8Decoder::new().map(u16::from)),
+            _ => Box::new(U16BeDecoder::new()),
+        }
+    }
+
+    #[test]
+    fn decoder() {
+        let mut decoder = handler(0);
+        assert_eq!(decoder.bytes_received(&[42, 21]).unwrap(), 1);
+        assert_eq!(decoder.end().unwrap(), 42);
+
+        let mut decoder = handler(1);
+        assert_eq!(decoder.bytes_received(&[1]).unwrap(), 1);
+        assert_eq!(decoder.bytes_received(&[0]).unwrap(), 1);
+        assert_eq!(decoder.end().unwrap(), 256);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn decode_sync_with() {
+        let mut reader: &[u8] = &[1, 0];
+        assert_eq!(crate::decode_sync_with(&mut reader, handler(1)).unwrap(), 256);
+    }
+
+    #[test]
+    fn encoder() {
+        let encoders: [Box<dyn DynEncoder>; 2] = [Box::new(IntEncoder::new_be(1u16)), Box::new(IntEncoder::new_le(2u32))];
+        let mut buf = Vec::new();
+        for encoder in encoders {
+            assert_eq!(encoder.encoded_len_hint(), Some(encoder.encoded_chunk().len()));
+            encoder.write_to_vec(&mut buf);
+        }
+        assert_eq!(buf, [0, 1, 2, 0, 0, 0]);
+    }
+}
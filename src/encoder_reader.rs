@@ -0,0 +1,154 @@
+use crate::{Encoder, EncoderPositionTracker};
+
+/// Adapts an encoder to reader traits.
+///
+/// This is useful when passing encoded bytes to APIs that pull the data from a reader. The
+/// [`BufRead`](std::io::BufRead) implementations and their async equivalents return the chunks
+/// produced by the encoder directly so they avoid copying.
+///
+/// The async traits are implemented for `tokio`, `futures_0_3` and `async-std`. Note that the
+/// `futures_0_3` and `async-std` traits are the same.
+#[derive(Debug)]
+pub struct EncoderReader<E> {
+    encoder: EncoderPositionTracker<E>,
+}
+
+impl<E: Encoder> EncoderReader<E> {
+    /// Creates the reader.
+    pub fn new(encoder: E) -> Self {
+        EncoderReader {
+            encoder: EncoderPositionTracker::new(encoder),
+        }
+    }
+
+    // Copies as many bytes as possible, returns the number of copied bytes.
+    fn read_into(&mut self, buf: &mut [u8]) -> usize {
+        let mut total = 0;
+        while total < buf.len() {
+            let chunk = self.encoder.encoded_chunk();
+            if chunk.is_empty() {
+                break;
+            }
+            let to_copy = chunk.len().min(buf.len() - total);
+            buf[total..(total + to_copy)].copy_from_slice(&chunk[..to_copy]);
+            self.encoder.consume(to_copy);
+            total += to_copy;
+        }
+        total
+    }
+}
+
+// There's no structural pinning.
+impl<E> Unpin for EncoderReader<E> {}
+
+impl<E: Encoder> std::io::Read for EncoderReader<E> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        Ok(self.read_into(buf))
+    }
+}
+
+impl<E: Encoder> std::io::BufRead for EncoderReader<E> {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        Ok(self.encoder.encoded_chunk())
+    }
+
+    fn consume(&mut self, amount: usize) {
+        self.encoder.consume(amount)
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<E: Encoder> actual_tokio::io::AsyncRead for EncoderReader<E> {
+    fn poll_read(self: core::pin::Pin<&mut Self>, _ctx: &mut core::task::Context, buf: &mut actual_tokio::io::ReadBuf) -> core::task::Poll<std::io::Result<()>> {
+        let this = self.get_mut();
+        loop {
+            let chunk = this.encoder.encoded_chunk();
+            let to_copy = chunk.len().min(buf.remaining());
+            if to_copy == 0 {
+                break;
+            }
+            buf.put_slice(&chunk[..to_copy]);
+            this.encoder.consume(to_copy);
+        }
+        core::task::Poll::Ready(Ok(()))
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<E: Encoder> actual_tokio::io::AsyncBufRead for EncoderReader<E> {
+    fn poll_fill_buf(self: core::pin::Pin<&mut Self>, _ctx: &mut core::task::Context) -> core::task::Poll<std::io::Result<&[u8]>> {
+        core::task::Poll::Ready(Ok(self.get_mut().encoder.encoded_chunk()))
+    }
+
+    fn consume(self: core::pin::Pin<&mut Self>, amount: usize) {
+        self.get_mut().encoder.consume(amount)
+    }
+}
+
+// async-std re-exports the traits from futures-io
+#[cfg(any(feature = "async-std", feature = "futures_0_3"))]
+macro_rules! impl_futures_read {
+    ($read:path, $buf_read:path) => {
+        impl<E: Encoder> $read for EncoderReader<E> {
+            fn poll_read(self: core::pin::Pin<&mut Self>, _ctx: &mut core::task::Context, buf: &mut [u8]) -> core::task::Poll<std::io::Result<usize>> {
+                core::task::Poll::Ready(Ok(self.get_mut().read_into(buf)))
+            }
+        }
+
+        impl<E: Encoder> $buf_read for EncoderReader<E> {
+            fn poll_fill_buf(self: core::pin::Pin<&mut Self>, _ctx: &mut core::task::Context) -> core::task::Poll<std::io::Result<&[u8]>> {
+                core::task::Poll::Ready(Ok(self.get_mut().encoder.encoded_chunk()))
+            }
+
+            fn consume(self: core::pin::Pin<&mut Self>, amount: usize) {
+                self.get_mut().encoder.consume(amount)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "async-std")]
+impl_futures_read!(actual_async_std::io::Read, actual_async_std::io::BufRead);
+
+#[cfg(all(feature = "futures_0_3", not(feature = "async-std")))]
+impl_futures_read!(futures_io_0_3::AsyncRead, futures_io_0_3::AsyncBufRead);
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, Read};
+    use super::EncoderReader;
+    use crate::Encoder;
+    use crate::encoders::BytesEncoder;
+
+    #[test]
+    fn read_to_end() {
+        let encoder = BytesEncoder::new([1, 2]).chain(BytesEncoder::new([3]));
+        let mut buf = alloc::vec::Vec::new();
+        EncoderReader::new(encoder).read_to_end(&mut buf).unwrap();
+        assert_eq!(buf, [1, 2, 3]);
+    }
+
+    #[test]
+    fn read_small_buffer() {
+        let encoder = BytesEncoder::new([1, 2]).chain(BytesEncoder::new([3]));
+        let mut reader = EncoderReader::new(encoder);
+        let mut buf = [0; 2];
+        assert_eq!(reader.read(&mut buf[..1]).unwrap(), 1);
+        assert_eq!(reader.read(&mut buf).unwrap(), 2);
+        assert_eq!(buf, [2, 3]);
+        assert_eq!(reader.read(&mut buf).unwrap(), 0);
+    }
+
+    #[test]
+    fn buf_read() {
+        let encoder = BytesEncoder::new([1, 2]).chain(BytesEncoder::new([3]));
+        let mut reader = EncoderReader::new(encoder);
+        assert_eq!(reader.fill_buf().unwrap(), [1, 2]);
+        reader.consume(1);
+        assert_eq!(reader.fill_buf().unwrap(), [2]);
+        reader.consume(1);
+        assert_eq!(reader.fill_buf().unwrap(), [3]);
+        reader.consume(1);
+        assert!(reader.fill_buf().unwrap().is_empty());
+    }
+}
@@ -1,9 +1,33 @@
 pub mod combinators;
 
 mod bytes;
+mod compact_size;
 mod int;
+mod float;
+mod impls;
+mod uint_n;
 mod iter;
+mod leb128;
+mod len_prefixed;
+mod option;
 
 pub use bytes::{ByteEncoder, BytesEncoder};
+pub use compact_size::CompactSizeEncoder;
 pub use int::IntEncoder;
+pub use float::FloatEncoder;
+pub use uint_n::UintNEncoder;
 pub use iter::IterEncoder;
+pub use leb128::Leb128Encoder;
+pub use len_prefixed::LengthPrefixed;
+pub use option::OptionEncoder;
+
+/// Implements [`Encoder::lookahead`](crate::Encoder::lookahead) for encoders with a single chunk.
+fn lookahead_single<'a>(chunk: &'a [u8], chunks: &mut [&'a [u8]]) -> (usize, bool) {
+    match chunks.first_mut() {
+        Some(first) if !chunk.is_empty() => {
+            *first = chunk;
+            (1, true)
+        },
+        _ => (0, chunk.is_empty()),
+    }
+}
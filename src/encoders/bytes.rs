@@ -1,4 +1,4 @@
-use crate::Encoder;
+use crate::{Encode, Encoder, ExactSizeEncoder};
 
 /// A convenient alias for encoding a single byte.
 pub type ByteEncoder = BytesEncoder<[u8; 1]>;
@@ -31,6 +31,20 @@ impl<T: AsRef<[u8]>> Encoder for BytesEncoder<T> {
     fn next(&mut self) -> bool {
         false
     }
+
+    fn encoded_len_hint(&self) -> Option<usize> {
+        Some(self.encoded_len())
+    }
+
+    fn lookahead<'a>(&'a self, chunks: &mut [&'a [u8]]) -> (usize, bool) {
+        super::lookahead_single(self.encoded_chunk(), chunks)
+    }
+}
+
+impl<T: AsRef<[u8]>> ExactSizeEncoder for BytesEncoder<T> {
+    fn encoded_len(&self) -> usize {
+        self.encoded_chunk().len()
+    }
 }
 
 impl<T: AsRef<[u8]>> From<T> for BytesEncoder<T> {
@@ -45,6 +59,31 @@ impl From<u8> for BytesEncoder<[u8; 1]> {
     }
 }
 
+impl<const N: usize> Encode for [u8; N] {
+    type Encoder = BytesEncoder<[u8; N]>;
+
+    fn encoder(self) -> Self::Encoder {
+        BytesEncoder::new(self)
+    }
+}
+
+impl<'a, const N: usize> Encode for &'a [u8; N] {
+    type Encoder = BytesEncoder<&'a [u8; N]>;
+
+    fn encoder(self) -> Self::Encoder {
+        BytesEncoder::new(self)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> Encode for &'a alloc::string::String {
+    type Encoder = super::combinators::Chain<super::Leb128Encoder, BytesEncoder<&'a str>>;
+
+    fn encoder(self) -> Self::Encoder {
+        super::Leb128Encoder::new(self.len() as u64).chain(BytesEncoder::new(self.as_str()))
+    }
+}
+
 #[cfg(feature = "alloc")]
 #[cfg(test)]
 mod tests {
@@ -68,4 +107,13 @@ mod tests {
         encoder.write_to_vec(&mut buf);
         assert_eq!(buf, [42]);
     }
+
+    #[test]
+    fn string() {
+        use crate::Encode;
+        let string = alloc::string::String::from("foo");
+        let mut buf = alloc::vec::Vec::new();
+        string.encoder().write_to_vec(&mut buf);
+        assert_eq!(buf, [3, b'f', b'o', b'o']);
+    }
 }
@@ -1,5 +1,7 @@
 mod chain;
+mod checksum;
 mod then;
 
 pub use chain::Chain;
+pub use checksum::AppendChecksum;
 pub use then::Then;
@@ -1,4 +1,4 @@
-use crate::Encoder;
+use crate::{Encode, Encoder, ExactSizeEncoder};
 
 #[derive(Debug, Clone)]
 pub struct Chain<A, B> {
@@ -42,8 +42,83 @@ impl<A: Encoder, B: Encoder> Encoder for Chain<A, B> {
             None => self.second.next(),
         }
     }
+
+    fn encoded_len_hint(&self) -> Option<usize> {
+        let first = match &self.first {
+            Some(first) => first.encoded_len_hint()?,
+            None => 0,
+        };
+        first.checked_add(self.second.encoded_len_hint()?)
+    }
+
+    fn lookahead<'a>(&'a self, chunks: &mut [&'a [u8]]) -> (usize, bool) {
+        let (count, complete) = match &self.first {
+            Some(first) => first.lookahead(chunks),
+            None => (0, true),
+        };
+        if !complete {
+            return (count, false);
+        }
+        let (second_count, complete) = self.second.lookahead(&mut chunks[count..]);
+        (count + second_count, complete)
+    }
+}
+
+impl<A: ExactSizeEncoder, B: ExactSizeEncoder> ExactSizeEncoder for Chain<A, B> {
+    /// Returns the sum of the lengths of both encoders.
+    ///
+    /// # Panics
+    ///
+    /// If the sum overflows `usize`.
+    fn encoded_len(&self) -> usize {
+        self.first.as_ref().map_or(0, ExactSizeEncoder::encoded_len)
+            .checked_add(self.second.encoded_len())
+            .expect("the encoded length overflowed usize")
+    }
+}
+
+macro_rules! chain_type {
+    ($last:ty) => { $last };
+    ($first:ty, $($rest:ty),+) => { Chain<$first, chain_type!($($rest),+)> };
+}
+
+macro_rules! chain_value {
+    ($last:expr) => { $last };
+    ($first:expr, $($rest:expr),+) => { Chain::new($first, chain_value!($($rest),+)) };
+}
+
+macro_rules! impl_encode_tuple {
+    ($($param:ident),+) => {
+        impl<$($param: Encode),+> Encode for ($($param,)+) {
+            type Encoder = chain_type!($($param::Encoder),+);
+
+            #[allow(non_snake_case)]
+            fn encoder(self) -> Self::Encoder {
+                let ($($param,)+) = self;
+                chain_value!($($param.encoder()),+)
+            }
+        }
+
+        impl<'a, $($param),+> Encode for &'a ($($param,)+) where $(&'a $param: Encode),+ {
+            type Encoder = chain_type!($(<&'a $param as Encode>::Encoder),+);
+
+            #[allow(non_snake_case)]
+            fn encoder(self) -> Self::Encoder {
+                let ($($param,)+) = self;
+                chain_value!($($param.encoder()),+)
+            }
+        }
+    }
 }
 
+impl_encode_tuple!(A, B);
+impl_encode_tuple!(A, B, C);
+impl_encode_tuple!(A, B, C, D);
+impl_encode_tuple!(A, B, C, D, E);
+impl_encode_tuple!(A, B, C, D, E, F);
+impl_encode_tuple!(A, B, C, D, E, F, G);
+impl_encode_tuple!(A, B, C, D, E, F, G, H);
+
 #[cfg(feature = "alloc")]
 #[cfg(test)]
 mod tests {
@@ -57,4 +132,50 @@ mod tests {
         encoder.write_to_vec(&mut buf);
         assert_eq!(buf, [1, 2]);
     }
+
+    #[test]
+    fn encoded_len() {
+        use crate::ExactSizeEncoder;
+        use crate::encoders::{BytesEncoder, IntEncoder};
+
+        let mut encoder = BytesEncoder::new([1, 2]).chain(IntEncoder::new_be(3u32));
+        assert_eq!(encoder.encoded_len(), 6);
+        assert_eq!(encoder.encoded_len_hint(), Some(6));
+        assert!(encoder.next());
+        assert_eq!(encoder.encoded_len(), 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "the encoded length overflowed usize")]
+    fn encoded_len_overflow() {
+        use crate::ExactSizeEncoder;
+        use crate::encoders::BytesEncoder;
+        use crate::test_utils::HugeEncoder;
+
+        BytesEncoder::new([1]).chain(HugeEncoder).encoded_len();
+    }
+
+    #[test]
+    fn lookahead() {
+        use crate::encoders::BytesEncoder;
+
+        let encoder = BytesEncoder::new([1, 2]).chain(BytesEncoder::new([3])).chain(BytesEncoder::new([4]));
+        let mut chunks = [&[][..]; 2];
+        assert_eq!(encoder.lookahead(&mut chunks), (2, false));
+        assert_eq!(chunks, [&[1, 2][..], &[3]]);
+        let mut chunks = [&[][..]; 4];
+        assert_eq!(encoder.lookahead(&mut chunks), (3, true));
+        assert_eq!(chunks[..3], [&[1, 2][..], &[3], &[4]]);
+    }
+
+    #[test]
+    fn tuple() {
+        use crate::Encode;
+        let mut buf = alloc::vec::Vec::new();
+        (1u8, [2u8, 3], 4u16).encoder().write_to_vec(&mut buf);
+        assert_eq!(buf, [1, 2, 3, 0, 4]);
+        buf.clear();
+        (&(1u8, 2u8)).encoder().write_to_vec(&mut buf);
+        assert_eq!(buf, [1, 2]);
+    }
 }
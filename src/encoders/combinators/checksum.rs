@@ -0,0 +1,170 @@
+use core::fmt;
+use crate::{Encoder, ExactSizeEncoder};
+use crate::checksum::Checksum;
+use super::super::lookahead_single;
+
+/// Encoder returned by [`Encoder::append_checksum`].
+pub struct AppendChecksum<E, C: Checksum> {
+    state: State<E, C>,
+}
+
+enum State<E, C: Checksum> {
+    // The current chunk of the encoder was already fed into the checksum.
+    Data(E, C),
+    Digest(C::Digest),
+    Done,
+}
+
+impl<E: Encoder, C: Checksum> AppendChecksum<E, C> {
+    pub(crate) fn new(encoder: E, mut checksum: C) -> Self {
+        let chunk = encoder.encoded_chunk();
+        if chunk.is_empty() {
+            return AppendChecksum { state: State::Digest(Self::finalize(checksum)) };
+        }
+        checksum.update(chunk);
+        AppendChecksum {
+            state: State::Data(encoder, checksum),
+        }
+    }
+
+    fn finalize(checksum: C) -> C::Digest {
+        let digest = checksum.finalize();
+        debug_assert_eq!(digest.as_ref().len(), C::LEN, "the digest length doesn't match `Checksum::LEN`");
+        digest
+    }
+}
+
+impl<E, C> fmt::Debug for AppendChecksum<E, C>
+where E: fmt::Debug, C: Checksum + fmt::Debug, C::Digest: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.state {
+            State::Data(encoder, checksum) => f.debug_tuple("AppendChecksum::Data").field(encoder).field(checksum).finish(),
+            State::Digest(digest) => f.debug_tuple("AppendChecksum::Digest").field(digest).finish(),
+            State::Done => f.debug_tuple("AppendChecksum::Done").finish(),
+        }
+    }
+}
+
+impl<E: Encoder, C: Checksum> Encoder for AppendChecksum<E, C> {
+    fn encoded_chunk(&self) -> &[u8] {
+        match &self.state {
+            State::Data(encoder, _) => encoder.encoded_chunk(),
+            State::Digest(digest) => digest.as_ref(),
+            State::Done => &[],
+        }
+    }
+
+    fn next(&mut self) -> bool {
+        match &mut self.state {
+            State::Data(encoder, checksum) => {
+                if encoder.next() {
+                    checksum.update(encoder.encoded_chunk());
+                    true
+                } else {
+                    let checksum = match core::mem::replace(&mut self.state, State::Done) {
+                        State::Data(_, checksum) => checksum,
+                        _ => unreachable!(),
+                    };
+                    let digest = Self::finalize(checksum);
+                    let is_empty = digest.as_ref().is_empty();
+                    self.state = State::Digest(digest);
+                    !is_empty
+                }
+            },
+            State::Digest(_) => {
+                self.state = State::Done;
+                false
+            },
+            State::Done => false,
+        }
+    }
+
+    fn encoded_len_hint(&self) -> Option<usize> {
+        match &self.state {
+            State::Data(encoder, _) => encoder.encoded_len_hint()?.checked_add(C::LEN),
+            State::Digest(digest) => Some(digest.as_ref().len()),
+            State::Done => Some(0),
+        }
+    }
+
+    fn lookahead<'a>(&'a self, chunks: &mut [&'a [u8]]) -> (usize, bool) {
+        match &self.state {
+            // The digest is not computed until the encoder is exhausted.
+            State::Data(encoder, _) => (encoder.lookahead(chunks).0, false),
+            State::Digest(digest) => lookahead_single(digest.as_ref(), chunks),
+            State::Done => (0, true),
+        }
+    }
+}
+
+impl<E: ExactSizeEncoder, C: Checksum> ExactSizeEncoder for AppendChecksum<E, C> {
+    /// Returns the length of the data and the digest.
+    ///
+    /// # Panics
+    ///
+    /// If the sum overflows `usize`.
+    fn encoded_len(&self) -> usize {
+        match &self.state {
+            State::Data(encoder, _) => encoder.encoded_len()
+                .checked_add(C::LEN)
+                .expect("the encoded length overflowed usize"),
+            State::Digest(digest) => digest.as_ref().len(),
+            State::Done => 0,
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+    use crate::{Encoder, ExactSizeEncoder};
+    use crate::encoders::{BytesEncoder, IntEncoder};
+    use crate::test_utils::{HugeEncoder, Sum};
+
+    #[test]
+    fn append() {
+        let encoder = BytesEncoder::new([1, 2]).chain(IntEncoder::new_be(0x0300u16)).append_checksum(Sum::default());
+        let mut buf = Vec::new();
+        encoder.write_to_vec(&mut buf);
+        assert_eq!(buf, [1, 2, 3, 0, 0, 6]);
+    }
+
+    #[test]
+    fn empty() {
+        let mut encoder = BytesEncoder::new([]).append_checksum(Sum::default());
+        assert_eq!(encoder.encoded_len(), 2);
+        assert_eq!(encoder.encoded_chunk(), [0, 0]);
+        assert!(!encoder.next());
+        assert!(encoder.encoded_chunk().is_empty());
+    }
+
+    #[test]
+    fn encoded_len() {
+        let mut encoder = BytesEncoder::new([1, 2]).chain(IntEncoder::new_be(3u8)).append_checksum(Sum::default());
+        assert_eq!(encoder.encoded_len(), 5);
+        assert_eq!(encoder.encoded_len_hint(), Some(5));
+        assert!(encoder.next());
+        assert_eq!(encoder.encoded_len(), 3);
+        assert!(encoder.next());
+        assert_eq!(encoder.encoded_len(), 2);
+        assert_eq!(encoder.encoded_chunk(), [0, 6]);
+    }
+
+    #[test]
+    #[should_panic(expected = "the encoded length overflowed usize")]
+    fn encoded_len_overflow() {
+        HugeEncoder.append_checksum(Sum::default()).encoded_len();
+    }
+
+    #[test]
+    fn lookahead() {
+        let mut encoder = BytesEncoder::new([1]).append_checksum(Sum::default());
+        assert_eq!(encoder.lookahead(&mut [&[][..]; 4]), (1, false));
+        assert!(encoder.next());
+        let mut chunks = [&[][..]; 4];
+        assert_eq!(encoder.lookahead(&mut chunks), (1, true));
+        assert_eq!(chunks[0], [0, 1]);
+    }
+}
@@ -42,6 +42,21 @@ impl<A: Encoder, B: Encoder, F: FnMut() -> B> Encoder for Then<A, B, F> {
             State::Second(encoder) => encoder.next(),
         }
     }
+
+    fn encoded_len_hint(&self) -> Option<usize> {
+        match &self.state {
+            // The second encoder doesn't exist yet.
+            State::First(_, _) => None,
+            State::Second(encoder) => encoder.encoded_len_hint(),
+        }
+    }
+
+    fn lookahead<'a>(&'a self, chunks: &mut [&'a [u8]]) -> (usize, bool) {
+        match &self.state {
+            State::First(encoder, _) => (encoder.lookahead(chunks).0, false),
+            State::Second(encoder) => encoder.lookahead(chunks),
+        }
+    }
 }
 
 #[cfg(feature = "alloc")]
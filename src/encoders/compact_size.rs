@@ -0,0 +1,105 @@
+use crate::{Encoder, ExactSizeEncoder};
+
+/// Encodes an integer as Bitcoin CompactSize.
+///
+/// The encoding is always minimal.
+#[derive(Debug, Clone)]
+pub struct CompactSizeEncoder {
+    buf: [u8; 9],
+    len: u8,
+}
+
+impl CompactSizeEncoder {
+    /// Creates the encoder.
+    pub fn new(value: u64) -> Self {
+        let mut buf = [0; 9];
+        let len = match value {
+            0..=0xFC => {
+                buf[0] = value as u8;
+                1
+            },
+            0xFD..=0xFFFF => {
+                buf[0] = 0xFD;
+                buf[1..3].copy_from_slice(&(value as u16).to_le_bytes());
+                3
+            },
+            0x10000..=0xFFFFFFFF => {
+                buf[0] = 0xFE;
+                buf[1..5].copy_from_slice(&(value as u32).to_le_bytes());
+                5
+            },
+            _ => {
+                buf[0] = 0xFF;
+                buf[1..9].copy_from_slice(&value.to_le_bytes());
+                9
+            },
+        };
+        CompactSizeEncoder {
+            buf,
+            len,
+        }
+    }
+}
+
+impl Encoder for CompactSizeEncoder {
+    fn encoded_chunk(&self) -> &[u8] {
+        &self.buf[..usize::from(self.len)]
+    }
+
+    fn next(&mut self) -> bool {
+        false
+    }
+
+    fn encoded_len_hint(&self) -> Option<usize> {
+        Some(self.encoded_len())
+    }
+
+    fn lookahead<'a>(&'a self, chunks: &mut [&'a [u8]]) -> (usize, bool) {
+        super::lookahead_single(self.encoded_chunk(), chunks)
+    }
+}
+
+impl ExactSizeEncoder for CompactSizeEncoder {
+    fn encoded_len(&self) -> usize {
+        self.encoded_chunk().len()
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg(test)]
+mod tests {
+    use super::CompactSizeEncoder;
+    use crate::Encoder;
+
+    #[track_caller]
+    fn expect(value: u64, expected: &[u8]) {
+        let mut buf = alloc::vec::Vec::new();
+        CompactSizeEncoder::new(value).write_to_vec(&mut buf);
+        assert_eq!(buf, expected);
+    }
+
+    #[test]
+    fn boundaries() {
+        expect(0, &[0x00]);
+        expect(0xFC, &[0xFC]);
+        expect(0xFD, &[0xFD, 0xFD, 0x00]);
+        expect(0xFFFF, &[0xFD, 0xFF, 0xFF]);
+        expect(0x10000, &[0xFE, 0x00, 0x00, 0x01, 0x00]);
+        expect(0xFFFFFFFF, &[0xFE, 0xFF, 0xFF, 0xFF, 0xFF]);
+        expect(0x100000000, &[0xFF, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00]);
+    }
+
+    #[test]
+    fn roundtrip() {
+        use crate::Decoder;
+        use crate::decoders::CompactSizeDecoder;
+
+        for value in [0, 0xFC, 0xFD, 0xFFFF, 0x10000, 0xFFFFFFFF, 0x100000000, u64::MAX] {
+            let mut buf = alloc::vec::Vec::new();
+            CompactSizeEncoder::new(value).write_to_vec(&mut buf);
+            let mut decoder = CompactSizeDecoder::new();
+            assert_eq!(decoder.bytes_received(&buf).unwrap(), buf.len());
+            assert_eq!(decoder.end().unwrap(), value);
+        }
+    }
+}
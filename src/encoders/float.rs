@@ -0,0 +1,118 @@
+use crate::int::*;
+use crate::{Encode, Encoder, ExactSizeEncoder};
+use super::IntEncoder;
+
+/// Encodes a floating-point number as its bit representation.
+///
+/// The `*_canonical` constructors replace all NaN values with the same quiet NaN so that the
+/// encoding of equivalent values is deterministic regardless of NaN sign or payload.
+pub struct FloatEncoder<T: Float>(IntEncoder<T::Bits>);
+
+impl<T: Float> FloatEncoder<T> {
+    /// Creates the encoder using the byte order `E`.
+    pub fn with_byte_order<E: ByteOrder>(value: T) -> Self {
+        FloatEncoder(IntEncoder::with_byte_order::<E>(value.to_bits()))
+    }
+
+    /// Creates the encoder using the byte order `E`, canonicalizing NaN.
+    pub fn with_byte_order_canonical<E: ByteOrder>(value: T) -> Self {
+        let bits = if value.is_nan() { T::CANONICAL_NAN_BITS } else { value.to_bits() };
+        FloatEncoder(IntEncoder::with_byte_order::<E>(bits))
+    }
+
+    /// Creates big-endian encoder.
+    pub fn new_be(value: T) -> Self {
+        Self::with_byte_order::<BigEndian>(value)
+    }
+
+    /// Creates little-endian encoder.
+    pub fn new_le(value: T) -> Self {
+        Self::with_byte_order::<LittleEndian>(value)
+    }
+
+    /// Creates big-endian encoder canonicalizing NaN.
+    pub fn new_be_canonical(value: T) -> Self {
+        Self::with_byte_order_canonical::<BigEndian>(value)
+    }
+
+    /// Creates little-endian encoder canonicalizing NaN.
+    pub fn new_le_canonical(value: T) -> Self {
+        Self::with_byte_order_canonical::<LittleEndian>(value)
+    }
+}
+
+impl<T: Float> Encoder for FloatEncoder<T> {
+    fn encoded_chunk(&self) -> &[u8] {
+        self.0.encoded_chunk()
+    }
+
+    fn next(&mut self) -> bool {
+        self.0.next()
+    }
+
+    fn encoded_len_hint(&self) -> Option<usize> {
+        self.0.encoded_len_hint()
+    }
+
+    fn lookahead<'a>(&'a self, chunks: &mut [&'a [u8]]) -> (usize, bool) {
+        self.0.lookahead(chunks)
+    }
+}
+
+impl<T: Float> ExactSizeEncoder for FloatEncoder<T> {
+    fn encoded_len(&self) -> usize {
+        self.0.encoded_len()
+    }
+}
+
+macro_rules! impl_encode {
+    ($($float:ty),+) => {
+        $(
+            impl Encode for $float {
+                type Encoder = FloatEncoder<$float>;
+
+                fn encoder(self) -> Self::Encoder {
+                    FloatEncoder::new_be(self)
+                }
+            }
+
+            impl Encode for &'_ $float {
+                type Encoder = FloatEncoder<$float>;
+
+                fn encoder(self) -> Self::Encoder {
+                    FloatEncoder::new_be(*self)
+                }
+            }
+        )+
+    }
+}
+
+impl_encode!(f32, f64);
+
+#[cfg(feature = "alloc")]
+#[cfg(test)]
+mod tests {
+    use super::FloatEncoder;
+    use crate::test_utils::encode;
+
+    #[test]
+    fn be_le() {
+        assert_eq!(encode(FloatEncoder::new_be(1.0f32)), [0x3F, 0x80, 0, 0]);
+        assert_eq!(encode(FloatEncoder::new_le(1.0f32)), [0, 0, 0x80, 0x3F]);
+        assert_eq!(encode(FloatEncoder::new_be(-2.5f64)), (-2.5f64).to_be_bytes());
+    }
+
+    #[test]
+    fn nan_preserved() {
+        let nan = f32::from_bits(0xFFC0_0001);
+        assert_eq!(encode(FloatEncoder::new_be(nan)), [0xFF, 0xC0, 0, 1]);
+    }
+
+    #[test]
+    fn nan_canonical() {
+        let nan = f32::from_bits(0xFFC0_0001);
+        assert_eq!(encode(FloatEncoder::new_be_canonical(nan)), [0x7F, 0xC0, 0, 0]);
+        assert_eq!(encode(FloatEncoder::new_le_canonical(-f64::NAN)), f64::NAN.to_le_bytes());
+        assert_eq!(encode(FloatEncoder::new_be_canonical(1.5f64)), 1.5f64.to_be_bytes());
+    }
+}
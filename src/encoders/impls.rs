@@ -0,0 +1,148 @@
+//! Implementations of encoder traits for foreign types.
+
+use either::Either;
+use crate::{Encoder, ExactSizeEncoder};
+
+/// Encodes using either of the encoders.
+impl<L: Encoder, R: Encoder> Encoder for Either<L, R> {
+    fn encoded_chunk(&self) -> &[u8] {
+        match self {
+            Either::Left(encoder) => encoder.encoded_chunk(),
+            Either::Right(encoder) => encoder.encoded_chunk(),
+        }
+    }
+
+    fn next(&mut self) -> bool {
+        match self {
+            Either::Left(encoder) => encoder.next(),
+            Either::Right(encoder) => encoder.next(),
+        }
+    }
+
+    fn encoded_len_hint(&self) -> Option<usize> {
+        match self {
+            Either::Left(encoder) => encoder.encoded_len_hint(),
+            Either::Right(encoder) => encoder.encoded_len_hint(),
+        }
+    }
+
+    fn lookahead<'a>(&'a self, chunks: &mut [&'a [u8]]) -> (usize, bool) {
+        match self {
+            Either::Left(encoder) => encoder.lookahead(chunks),
+            Either::Right(encoder) => encoder.lookahead(chunks),
+        }
+    }
+}
+
+impl<L: ExactSizeEncoder, R: ExactSizeEncoder> ExactSizeEncoder for Either<L, R> {
+    fn encoded_len(&self) -> usize {
+        match self {
+            Either::Left(encoder) => encoder.encoded_len(),
+            Either::Right(encoder) => encoder.encoded_len(),
+        }
+    }
+}
+
+/// Encodes the value if present or nothing if it's `None`.
+///
+/// Unlike [`OptionEncoder`](super::OptionEncoder) this doesn't encode any tag so the decoder has
+/// to know whether the value is present from elsewhere, e.g. a flag or the end of the message.
+impl<E: Encoder> Encoder for Option<E> {
+    fn encoded_chunk(&self) -> &[u8] {
+        match self {
+            Some(encoder) => encoder.encoded_chunk(),
+            None => &[],
+        }
+    }
+
+    fn next(&mut self) -> bool {
+        match self {
+            Some(encoder) => encoder.next(),
+            None => false,
+        }
+    }
+
+    fn encoded_len_hint(&self) -> Option<usize> {
+        match self {
+            Some(encoder) => encoder.encoded_len_hint(),
+            None => Some(0),
+        }
+    }
+
+    fn lookahead<'a>(&'a self, chunks: &mut [&'a [u8]]) -> (usize, bool) {
+        match self {
+            Some(encoder) => encoder.lookahead(chunks),
+            None => (0, true),
+        }
+    }
+}
+
+impl<E: ExactSizeEncoder> ExactSizeEncoder for Option<E> {
+    fn encoded_len(&self) -> usize {
+        self.as_ref().map_or(0, ExactSizeEncoder::encoded_len)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<E: Encoder> Encoder for alloc::boxed::Box<E> {
+    fn encoded_chunk(&self) -> &[u8] {
+        (**self).encoded_chunk()
+    }
+
+    fn next(&mut self) -> bool {
+        (**self).next()
+    }
+
+    fn encoded_len_hint(&self) -> Option<usize> {
+        (**self).encoded_len_hint()
+    }
+
+    fn lookahead<'a>(&'a self, chunks: &mut [&'a [u8]]) -> (usize, bool) {
+        (**self).lookahead(chunks)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<E: ExactSizeEncoder> ExactSizeEncoder for alloc::boxed::Box<E> {
+    fn encoded_len(&self) -> usize {
+        (**self).encoded_len()
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg(test)]
+mod tests {
+    use alloc::boxed::Box;
+    use either::Either;
+    use crate::{Encoder, ExactSizeEncoder};
+    use crate::encoders::{BytesEncoder, IntEncoder};
+    use crate::test_utils::encode;
+
+    #[test]
+    fn either() {
+        let encoder = |flag| if flag {
+            Either::Left(IntEncoder::new_be(1u16))
+        } else {
+            Either::Right(BytesEncoder::new([1, 2, 3]))
+        };
+        assert_eq!(encoder(true).encoded_len(), 2);
+        assert_eq!(encode(encoder(true)), [0, 1]);
+        assert_eq!(encode(encoder(false)), [1, 2, 3]);
+    }
+
+    #[test]
+    fn optional_trailing_field() {
+        let encoder = |value: Option<u16>| IntEncoder::new_be(42u8).chain(value.map(IntEncoder::new_be));
+        assert_eq!(encoder(Some(1)).encoded_len(), 3);
+        assert_eq!(encode(encoder(Some(1))), [42, 0, 1]);
+        assert_eq!(encoder(None).encoded_len(), 1);
+        assert_eq!(encode(encoder(None)), [42]);
+    }
+
+    #[test]
+    fn boxed() {
+        let encoder = Box::new(IntEncoder::new_be(1u16));
+        assert_eq!(encoder.encoded_len(), 2);
+        assert_eq!(encode(encoder), [0, 1]);
+    }
+}
@@ -1,19 +1,29 @@
 use core::marker::PhantomData;
 use crate::int::*;
-use crate::Encoder;
+use crate::{Encode, Encoder, ExactSizeEncoder};
 
 /// Encodes an integer as an array of bytes.
 pub struct IntEncoder<T: Int>(T::Bytes, PhantomData<fn(T)>);
 
 impl<T: Int> IntEncoder<T> {
+    /// Creates the encoder using the byte order `E`.
+    pub fn with_byte_order<E: ByteOrder>(value: T) -> Self {
+        IntEncoder(E::to_bytes(value), Default::default())
+    }
+
     /// Creates big-endian encoder.
     pub fn new_be(value: T) -> Self {
-        IntEncoder(value.to_be_bytes(), Default::default())
+        Self::with_byte_order::<BigEndian>(value)
     }
 
     /// Creates little-endian encoder.
     pub fn new_le(value: T) -> Self {
-        IntEncoder(value.to_le_bytes(), Default::default())
+        Self::with_byte_order::<LittleEndian>(value)
+    }
+
+    /// Creates native-endian encoder.
+    pub fn new_ne(value: T) -> Self {
+        Self::with_byte_order::<NativeEndian>(value)
     }
 }
 
@@ -25,8 +35,46 @@ impl<T: Int> Encoder for IntEncoder<T> {
     fn next(&mut self) -> bool {
         false
     }
+
+    fn encoded_len_hint(&self) -> Option<usize> {
+        Some(self.encoded_len())
+    }
+
+    fn lookahead<'a>(&'a self, chunks: &mut [&'a [u8]]) -> (usize, bool) {
+        super::lookahead_single(self.encoded_chunk(), chunks)
+    }
+}
+
+impl<T: Int> ExactSizeEncoder for IntEncoder<T> {
+    fn encoded_len(&self) -> usize {
+        self.encoded_chunk().len()
+    }
 }
 
+macro_rules! impl_encode {
+    ($($int:ty),+) => {
+        $(
+            impl Encode for $int {
+                type Encoder = IntEncoder<$int>;
+
+                fn encoder(self) -> Self::Encoder {
+                    IntEncoder::new_be(self)
+                }
+            }
+
+            impl Encode for &'_ $int {
+                type Encoder = IntEncoder<$int>;
+
+                fn encoder(self) -> Self::Encoder {
+                    IntEncoder::new_be(*self)
+                }
+            }
+        )+
+    }
+}
+
+impl_encode!(u8, i8, u16, i16, u32, i32, u64, i64, u128, i128);
+
 #[cfg(feature = "alloc")]
 #[cfg(test)]
 mod tests {
@@ -68,12 +68,46 @@ impl<I: Iterator, T: Encoder + From<I::Item>> Encoder for IterEncoder<I, T> {
             EncoderState::Done => false,
         }
     }
+
+    fn encoded_len_hint(&self) -> Option<usize> {
+        match &self.0 {
+            // The lengths of the remaining items are only known once they are converted.
+            EncoderState::Encoding { current, remaining } if remaining.size_hint().1 == Some(0) => current.encoded_len_hint(),
+            EncoderState::Encoding { .. } => None,
+            EncoderState::Done => Some(0),
+        }
+    }
+
+    fn lookahead<'a>(&'a self, chunks: &mut [&'a [u8]]) -> (usize, bool) {
+        match &self.0 {
+            EncoderState::Encoding { current, remaining } => {
+                let (count, complete) = current.lookahead(chunks);
+                (count, complete && remaining.size_hint().1 == Some(0))
+            },
+            EncoderState::Done => (0, true),
+        }
+    }
+}
+
+/// The iterator used to encode the elements of a `Vec`.
+#[cfg(feature = "alloc")]
+type VecElements<'a, T> = core::iter::Map<core::slice::Iter<'a, T>, fn(&'a T) -> <&'a T as crate::Encode>::Encoder>;
+
+#[cfg(feature = "alloc")]
+impl<'a, T> crate::Encode for &'a alloc::vec::Vec<T> where &'a T: crate::Encode {
+    type Encoder = super::combinators::Chain<super::Leb128Encoder, IterEncoder<VecElements<'a, T>>>;
+
+    fn encoder(self) -> Self::Encoder {
+        let elements = self.iter().map(crate::Encode::encoder as fn(&'a T) -> <&'a T as crate::Encode>::Encoder);
+        super::Leb128Encoder::new(self.len() as u64).chain(IterEncoder::new(elements))
+    }
 }
 
 #[cfg(all(test, feature = "alloc"))]
 mod tests {
     use super::*;
     use super::super::BytesEncoder;
+    use crate::Encode;
 
     #[track_caller]
     fn expect(encoder: impl Encoder, expected: &[u8]) {
@@ -120,4 +154,20 @@ mod tests {
         let encoder = IterEncoder::<_, BytesEncoder<_>>::new(items);
         expect(encoder, &[1, 2]);
     }
+
+    #[test]
+    fn vec() {
+        let items = alloc::vec![1u16, 2];
+        expect(items.encoder(), &[2, 0, 1, 0, 2]);
+        expect(alloc::vec::Vec::<u16>::new().encoder(), &[0]);
+    }
+
+    #[test]
+    fn encoded_len_hint() {
+        let mut encoder = IterEncoder::<_, BytesEncoder<_>>::new([[1u8; 2], [2; 2]]);
+        assert_eq!(encoder.encoded_len_hint(), None);
+        assert!(encoder.next());
+        assert_eq!(encoder.encoded_len_hint(), Some(2));
+        assert!(!encoder.next());
+    }
 }
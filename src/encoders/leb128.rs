@@ -0,0 +1,131 @@
+use crate::int::{Signed, Unsigned};
+use crate::{Encoder, ExactSizeEncoder};
+
+// ceil(128 / 7)
+const MAX_LEN: usize = 19;
+
+/// Encodes an integer as LEB128 (varint).
+///
+/// The encoding is always minimal.
+#[derive(Debug, Clone)]
+pub struct Leb128Encoder {
+    buf: [u8; MAX_LEN],
+    len: u8,
+}
+
+impl Leb128Encoder {
+    /// Creates the encoder for an unsigned integer.
+    pub fn new<T: Unsigned>(value: T) -> Self {
+        Self::from_u128(value.into_u128())
+    }
+
+    /// Creates the encoder for a signed integer using zigzag encoding.
+    pub fn new_zigzag<T: Signed>(value: T) -> Self {
+        let value = value.into_i128();
+        Self::from_u128(((value << 1) ^ (value >> 127)) as u128)
+    }
+
+    fn from_u128(mut value: u128) -> Self {
+        let mut buf = [0; MAX_LEN];
+        let mut len = 0;
+        loop {
+            let byte = (value & 0x7F) as u8;
+            value >>= 7;
+            if value == 0 {
+                buf[len] = byte;
+                len += 1;
+                break;
+            }
+            buf[len] = byte | 0x80;
+            len += 1;
+        }
+        Leb128Encoder {
+            buf,
+            len: len as u8,
+        }
+    }
+}
+
+impl Encoder for Leb128Encoder {
+    fn encoded_chunk(&self) -> &[u8] {
+        &self.buf[..usize::from(self.len)]
+    }
+
+    fn next(&mut self) -> bool {
+        false
+    }
+
+    fn encoded_len_hint(&self) -> Option<usize> {
+        Some(self.encoded_len())
+    }
+
+    fn lookahead<'a>(&'a self, chunks: &mut [&'a [u8]]) -> (usize, bool) {
+        super::lookahead_single(self.encoded_chunk(), chunks)
+    }
+}
+
+impl ExactSizeEncoder for Leb128Encoder {
+    fn encoded_len(&self) -> usize {
+        self.encoded_chunk().len()
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg(test)]
+mod tests {
+    use super::Leb128Encoder;
+    use crate::Encoder;
+
+    #[track_caller]
+    fn expect(encoder: Leb128Encoder, expected: &[u8]) {
+        let mut buf = alloc::vec::Vec::new();
+        encoder.write_to_vec(&mut buf);
+        assert_eq!(buf, expected);
+    }
+
+    #[test]
+    fn unsigned() {
+        expect(Leb128Encoder::new(0u8), &[0x00]);
+        expect(Leb128Encoder::new(127u8), &[0x7F]);
+        expect(Leb128Encoder::new(128u16), &[0x80, 0x01]);
+        expect(Leb128Encoder::new(624485u32), &[0xE5, 0x8E, 0x26]);
+    }
+
+    #[test]
+    fn max() {
+        let mut expected = [0xFF; 19];
+        expected[18] = 0x03;
+        expect(Leb128Encoder::new(u128::MAX), &expected);
+    }
+
+    #[test]
+    fn zigzag() {
+        expect(Leb128Encoder::new_zigzag(0i32), &[0x00]);
+        expect(Leb128Encoder::new_zigzag(-1i32), &[0x01]);
+        expect(Leb128Encoder::new_zigzag(1i32), &[0x02]);
+        expect(Leb128Encoder::new_zigzag(i32::MIN), &[0xFF, 0xFF, 0xFF, 0xFF, 0x0F]);
+        expect(Leb128Encoder::new_zigzag(i128::MIN), &{ let mut max = [0xFF; 19]; max[18] = 0x03; max });
+    }
+
+    #[test]
+    fn roundtrip() {
+        use crate::Decoder;
+        use crate::decoders::{Leb128Decoder, ZigZagLeb128Decoder};
+
+        for value in [0u64, 1, 127, 128, 300, u32::MAX.into(), u64::MAX] {
+            let mut buf = alloc::vec::Vec::new();
+            Leb128Encoder::new(value).write_to_vec(&mut buf);
+            let mut decoder = Leb128Decoder::<u64>::new();
+            assert_eq!(decoder.bytes_received(&buf).unwrap(), buf.len());
+            assert_eq!(decoder.end().unwrap(), value);
+        }
+
+        for value in [0i64, -1, 1, -64, 64, i64::MIN, i64::MAX] {
+            let mut buf = alloc::vec::Vec::new();
+            Leb128Encoder::new_zigzag(value).write_to_vec(&mut buf);
+            let mut decoder = ZigZagLeb128Decoder::<i64>::new();
+            assert_eq!(decoder.bytes_received(&buf).unwrap(), buf.len());
+            assert_eq!(decoder.end().unwrap(), value);
+        }
+    }
+}
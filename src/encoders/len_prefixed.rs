@@ -0,0 +1,113 @@
+use core::convert::TryFrom;
+use crate::{Encoder, ExactSizeEncoder};
+use crate::encoders::{CompactSizeEncoder, IntEncoder, Leb128Encoder};
+use crate::encoders::combinators::Chain;
+use crate::error::LengthOverflow;
+use crate::int::Int;
+
+/// Encodes the length of the value followed by the value itself.
+///
+/// The length is the number of bytes produced by the inner encoder, so it's computed upfront
+/// without encoding the value twice.
+#[must_use = "encoders do nothing by themselves"]
+#[derive(Debug, Clone)]
+pub struct LengthPrefixed<P, E>(Chain<P, E>);
+
+impl<E: ExactSizeEncoder> LengthPrefixed<Leb128Encoder, E> {
+    /// Creates the encoder using LEB128-encoded length.
+    pub fn new_leb128(encoder: E) -> Self {
+        let prefix = Leb128Encoder::new(encoder.encoded_len() as u64);
+        LengthPrefixed(prefix.chain(encoder))
+    }
+}
+
+impl<E: ExactSizeEncoder> LengthPrefixed<CompactSizeEncoder, E> {
+    /// Creates the encoder using Bitcoin CompactSize-encoded length.
+    pub fn new_compact_size(encoder: E) -> Self {
+        let prefix = CompactSizeEncoder::new(encoder.encoded_len() as u64);
+        LengthPrefixed(prefix.chain(encoder))
+    }
+}
+
+impl<T: Int + TryFrom<usize>, E: ExactSizeEncoder> LengthPrefixed<IntEncoder<T>, E> {
+    /// Creates the encoder using big-endian length.
+    ///
+    /// Returns an error if the length doesn't fit into `T`.
+    pub fn new_be(encoder: E) -> Result<Self, LengthOverflow> {
+        let len = encoder.encoded_len();
+        let prefix = T::try_from(len).map_err(|_| LengthOverflow { len })?;
+        Ok(LengthPrefixed(IntEncoder::new_be(prefix).chain(encoder)))
+    }
+
+    /// Creates the encoder using little-endian length.
+    ///
+    /// Returns an error if the length doesn't fit into `T`.
+    pub fn new_le(encoder: E) -> Result<Self, LengthOverflow> {
+        let len = encoder.encoded_len();
+        let prefix = T::try_from(len).map_err(|_| LengthOverflow { len })?;
+        Ok(LengthPrefixed(IntEncoder::new_le(prefix).chain(encoder)))
+    }
+}
+
+impl<P: Encoder, E: Encoder> Encoder for LengthPrefixed<P, E> {
+    fn encoded_chunk(&self) -> &[u8] {
+        self.0.encoded_chunk()
+    }
+
+    fn next(&mut self) -> bool {
+        self.0.next()
+    }
+
+    fn encoded_len_hint(&self) -> Option<usize> {
+        self.0.encoded_len_hint()
+    }
+
+    fn lookahead<'a>(&'a self, chunks: &mut [&'a [u8]]) -> (usize, bool) {
+        self.0.lookahead(chunks)
+    }
+}
+
+impl<P: ExactSizeEncoder, E: ExactSizeEncoder> ExactSizeEncoder for LengthPrefixed<P, E> {
+    fn encoded_len(&self) -> usize {
+        self.0.encoded_len()
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg(test)]
+mod tests {
+    use super::LengthPrefixed;
+    use crate::{Encoder, ExactSizeEncoder};
+    use crate::encoders::{BytesEncoder, IntEncoder};
+
+    #[track_caller]
+    fn expect(encoder: impl Encoder, expected: &[u8]) {
+        let mut buf = alloc::vec::Vec::new();
+        encoder.write_to_vec(&mut buf);
+        assert_eq!(buf, expected);
+    }
+
+    #[test]
+    fn leb128() {
+        let encoder = LengthPrefixed::new_leb128(BytesEncoder::new([1, 2, 3]));
+        assert_eq!(encoder.encoded_len(), 4);
+        expect(encoder, &[3, 1, 2, 3]);
+    }
+
+    #[test]
+    fn compact_size() {
+        expect(LengthPrefixed::new_compact_size(BytesEncoder::new([0u8; 0])), &[0]);
+    }
+
+    #[test]
+    fn int() {
+        let payload = BytesEncoder::new([42]).chain(IntEncoder::new_be(1u16));
+        expect(LengthPrefixed::<IntEncoder<u16>, _>::new_be(payload).unwrap(), &[0, 3, 42, 0, 1]);
+        expect(LengthPrefixed::<IntEncoder<u16>, _>::new_le(BytesEncoder::new([42])).unwrap(), &[1, 0, 42]);
+    }
+
+    #[test]
+    fn overflow() {
+        assert!(LengthPrefixed::<IntEncoder<u8>, _>::new_be(BytesEncoder::new([0u8; 256])).is_err());
+    }
+}
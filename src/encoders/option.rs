@@ -0,0 +1,114 @@
+use crate::{Encode, Encoder, ExactSizeEncoder};
+
+/// Encodes an optional value prefixed by a tag byte.
+///
+/// `None` is encoded as a single `0` byte, `Some` as `1` followed by the value.
+#[must_use = "encoders do nothing by themselves"]
+#[derive(Debug, Clone)]
+pub struct OptionEncoder<E: Encoder> {
+    tag: [u8; 1],
+    tag_done: bool,
+    value: Option<E>,
+}
+
+impl<E: Encoder> OptionEncoder<E> {
+    /// Creates the encoder.
+    pub fn new(value: Option<E>) -> Self {
+        OptionEncoder {
+            tag: [value.is_some().into()],
+            tag_done: false,
+            value,
+        }
+    }
+}
+
+impl<E: Encoder> Encoder for OptionEncoder<E> {
+    fn encoded_chunk(&self) -> &[u8] {
+        if !self.tag_done {
+            return &self.tag;
+        }
+        match &self.value {
+            Some(value) => value.encoded_chunk(),
+            None => &[],
+        }
+    }
+
+    fn next(&mut self) -> bool {
+        if !self.tag_done {
+            self.tag_done = true;
+            return self.value.as_ref().map_or(false, |value| !value.encoded_chunk().is_empty());
+        }
+        match &mut self.value {
+            Some(value) => value.next(),
+            None => false,
+        }
+    }
+
+    fn encoded_len_hint(&self) -> Option<usize> {
+        let value = match &self.value {
+            Some(value) => value.encoded_len_hint()?,
+            None => 0,
+        };
+        Some(value + usize::from(!self.tag_done))
+    }
+
+    fn lookahead<'a>(&'a self, chunks: &mut [&'a [u8]]) -> (usize, bool) {
+        let mut count = 0;
+        if !self.tag_done {
+            match chunks.first_mut() {
+                Some(first) => *first = &self.tag,
+                None => return (0, false),
+            }
+            count = 1;
+        }
+        match &self.value {
+            Some(value) => {
+                let (value_count, complete) = value.lookahead(&mut chunks[count..]);
+                (count + value_count, complete)
+            },
+            None => (count, true),
+        }
+    }
+}
+
+impl<E: ExactSizeEncoder> ExactSizeEncoder for OptionEncoder<E> {
+    fn encoded_len(&self) -> usize {
+        self.value.as_ref().map_or(0, ExactSizeEncoder::encoded_len) + usize::from(!self.tag_done)
+    }
+}
+
+impl<'a, T> Encode for &'a Option<T> where &'a T: Encode {
+    type Encoder = OptionEncoder<<&'a T as Encode>::Encoder>;
+
+    fn encoder(self) -> Self::Encoder {
+        OptionEncoder::new(self.as_ref().map(Encode::encoder))
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg(test)]
+mod tests {
+    use crate::{Encode, Encoder};
+
+    #[track_caller]
+    fn expect(encoder: impl Encoder, expected: &[u8]) {
+        let mut buf = alloc::vec::Vec::new();
+        encoder.write_to_vec(&mut buf);
+        assert_eq!(buf, expected);
+    }
+
+    #[test]
+    fn none() {
+        expect((&None::<u16>).encoder(), &[0]);
+    }
+
+    #[test]
+    fn some() {
+        expect((&Some(42u16)).encoder(), &[1, 0, 42]);
+    }
+
+    #[test]
+    fn some_empty() {
+        expect((&Some([0u8; 0])).encoder(), &[1]);
+    }
+}
@@ -0,0 +1,93 @@
+use crate::int::*;
+use crate::{Encoder, ExactSizeEncoder};
+use crate::error::IntOverflow;
+
+/// Encodes an unsigned integer as `N` bytes.
+///
+/// This is the counterpart of [`UintNDecoder`](crate::decoders::UintNDecoder).
+#[must_use = "encoders do nothing by themselves"]
+#[derive(Debug, Clone)]
+pub struct UintNEncoder<const N: usize>([u8; N]);
+
+impl<const N: usize> UintNEncoder<N> {
+    /// Creates the encoder using the byte order `E`.
+    ///
+    /// Returns an error if the value doesn't fit into `N` bytes.
+    ///
+    /// # Panics
+    ///
+    /// If `N` is larger than 16.
+    pub fn with_byte_order<T: Unsigned, E: ByteOrder>(value: T) -> Result<Self, IntOverflow> {
+        assert!(N <= 16, "integers wider than 16 bytes are not supported");
+        let value = value.into_u128();
+        if N < 16 && value >> (N * 8) != 0 {
+            return Err(IntOverflow { value, bytes: N });
+        }
+        let mut bytes = [0; N];
+        E::uint_to_bytes(value, &mut bytes);
+        Ok(UintNEncoder(bytes))
+    }
+
+    /// Creates big-endian encoder.
+    ///
+    /// Returns an error if the value doesn't fit into `N` bytes.
+    pub fn new_be<T: Unsigned>(value: T) -> Result<Self, IntOverflow> {
+        Self::with_byte_order::<T, BigEndian>(value)
+    }
+
+    /// Creates little-endian encoder.
+    ///
+    /// Returns an error if the value doesn't fit into `N` bytes.
+    pub fn new_le<T: Unsigned>(value: T) -> Result<Self, IntOverflow> {
+        Self::with_byte_order::<T, LittleEndian>(value)
+    }
+}
+
+impl<const N: usize> Encoder for UintNEncoder<N> {
+    fn encoded_chunk(&self) -> &[u8] {
+        &self.0
+    }
+
+    fn next(&mut self) -> bool {
+        false
+    }
+
+    fn encoded_len_hint(&self) -> Option<usize> {
+        Some(N)
+    }
+
+    fn lookahead<'a>(&'a self, chunks: &mut [&'a [u8]]) -> (usize, bool) {
+        super::lookahead_single(self.encoded_chunk(), chunks)
+    }
+}
+
+impl<const N: usize> ExactSizeEncoder for UintNEncoder<N> {
+    fn encoded_len(&self) -> usize {
+        N
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::UintNEncoder;
+    use crate::Encoder;
+
+    #[test]
+    fn u24() {
+        assert_eq!(UintNEncoder::<3>::new_be(0x010203u32).unwrap().encoded_chunk(), [1, 2, 3]);
+        assert_eq!(UintNEncoder::<3>::new_le(0x010203u32).unwrap().encoded_chunk(), [3, 2, 1]);
+        assert_eq!(UintNEncoder::<3>::new_be(0xFFFFFFu64).unwrap().encoded_chunk(), [0xFF; 3]);
+    }
+
+    #[test]
+    fn overflow() {
+        let error = UintNEncoder::<3>::new_be(0x01000000u32).unwrap_err();
+        assert_eq!(error.value, 0x01000000);
+        assert_eq!(error.bytes, 3);
+    }
+
+    #[test]
+    fn full_width() {
+        assert_eq!(UintNEncoder::<16>::new_le(u128::MAX).unwrap().encoded_chunk(), [0xFF; 16]);
+    }
+}
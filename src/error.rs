@@ -31,3 +31,133 @@ impl fmt::Display for BufferOverflow {
 
 #[cfg(feature = "std")]
 impl std::error::Error for BufferOverflow {}
+
+/// Error returned when the length of a value doesn't fit into its length prefix.
+#[derive(Debug, Clone)]
+pub struct LengthOverflow {
+    pub(crate) len: usize,
+}
+
+impl LengthOverflow {
+    /// Creates the error for a value having length `len`.
+    pub fn new(len: usize) -> Self {
+        LengthOverflow { len }
+    }
+}
+
+impl fmt::Display for LengthOverflow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "the length {} doesn't fit into the length prefix", self.len)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for LengthOverflow {}
+
+/// Error returned when an integer doesn't fit into the number of bytes it's encoded as.
+#[derive(Debug, Clone)]
+pub struct IntOverflow {
+    pub(crate) value: u128,
+    pub(crate) bytes: usize,
+}
+
+impl fmt::Display for IntOverflow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "the integer {} doesn't fit into {} bytes", self.value, self.bytes)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for IntOverflow {}
+
+/// Error returned when decoding LEB128-encoded integer fails.
+#[derive(Debug, Clone)]
+pub enum Leb128Error {
+    /// The stream ended before the last byte of the integer was received.
+    UnexpectedEnd(UnexpectedEnd),
+    /// The encoded value doesn't fit into the target type.
+    Overflow,
+    /// The value was encoded using more bytes than necessary.
+    OverlongEncoding,
+}
+
+impl fmt::Display for Leb128Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Leb128Error::UnexpectedEnd(_) => write!(f, "unexpected end"),
+            Leb128Error::Overflow => write!(f, "the encoded integer is out of range"),
+            Leb128Error::OverlongEncoding => write!(f, "the integer is not minimally encoded"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Leb128Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Leb128Error::UnexpectedEnd(error) => Some(error),
+            Leb128Error::Overflow | Leb128Error::OverlongEncoding => None,
+        }
+    }
+}
+
+/// Error returned when decoding Bitcoin CompactSize-encoded integer fails.
+#[derive(Debug, Clone)]
+pub enum CompactSizeError {
+    /// The stream ended before the whole integer was received.
+    UnexpectedEnd(UnexpectedEnd),
+    /// The value was encoded using more bytes than necessary.
+    NonMinimal {
+        /// The decoded value.
+        value: u64,
+    },
+}
+
+impl fmt::Display for CompactSizeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CompactSizeError::UnexpectedEnd(_) => write!(f, "unexpected end"),
+            CompactSizeError::NonMinimal { value } => write!(f, "the value {} is not minimally encoded", value),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CompactSizeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            CompactSizeError::UnexpectedEnd(error) => Some(error),
+            CompactSizeError::NonMinimal { .. } => None,
+        }
+    }
+}
+
+/// Error returned when decoding a sequence of values fails.
+#[derive(Debug, Clone)]
+pub enum SequenceError<E> {
+    /// Decoding a value failed.
+    Decode(E),
+    /// The decoder finished a value without consuming any bytes.
+    ///
+    /// Continuing would produce the same empty value forever.
+    NoProgress,
+}
+
+impl<E> fmt::Display for SequenceError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SequenceError::Decode(_) => write!(f, "failed to decode a value"),
+            SequenceError::NoProgress => write!(f, "the decoder finished a value without consuming any bytes"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: std::error::Error + 'static> std::error::Error for SequenceError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SequenceError::Decode(error) => Some(error),
+            SequenceError::NoProgress => None,
+        }
+    }
+}
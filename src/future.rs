@@ -4,6 +4,7 @@ use std::task::{Context, Poll};
 use std::future::Future;
 use std::io;
 use super::{Decoder, Encoder, EncoderPositionTracker, ReadError};
+use crate::error::SequenceError;
 
 macro_rules! impl_encoder {
     ($future:ident, $trait:path, $poll_write:ident) => {
@@ -37,8 +38,8 @@ macro_rules! impl_encoder {
         struct $poll_write;
 
         impl<Writer: $trait> PollWrite<Writer> for $poll_write {
-            fn poll_write(writer: Pin<&mut Writer>, ctx: &mut Context, bytes: &[u8]) -> Poll<io::Result<usize>> {
-                writer.poll_write(ctx, bytes)
+            fn poll_write_vectored(writer: Pin<&mut Writer>, ctx: &mut Context, bufs: &[io::IoSlice]) -> Poll<io::Result<usize>> {
+                writer.poll_write_vectored(ctx, bufs)
             }
         }
     }
@@ -104,6 +105,71 @@ impl<T, PollFn, ConsumeFn, D> Future for DecodeFuture<T, PollFn, ConsumeFn, D> w
     }
 }
 
+pin_project! {
+    pub(crate) struct DecodeStream<T, PollFn, ConsumeFn, D> {
+        #[pin]
+        pub(crate) reader: T,
+        pub(crate) poll_fn: PollFn,
+        pub(crate) consume_fn: ConsumeFn,
+        pub(crate) decoder: D,
+        // Set when the decoder received at least one byte of the current value.
+        pub(crate) in_progress: bool,
+        pub(crate) done: bool,
+    }
+}
+
+impl<T, PollFn, ConsumeFn, D> futures_core_0_3::Stream for DecodeStream<T, PollFn, ConsumeFn, D> where
+    PollFn: for<'a> FnMut(Pin<&'a mut T>, &mut Context) -> Poll<io::Result<&'a [u8]>>,
+    ConsumeFn: FnMut(Pin<&mut T>, usize),
+    D: Decoder + Default,
+{
+    type Item = Result<D::Value, ReadError<SequenceError<D::Error>>>;
+
+    fn poll_next(self: Pin<&mut Self>, ctx: &mut Context) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+        while !*this.done {
+            let buf = match (this.poll_fn)(this.reader.as_mut(), ctx) {
+                Poll::Ready(Ok(buf)) => buf,
+                Poll::Ready(Err(error)) => {
+                    *this.done = true;
+                    return Poll::Ready(Some(Err(ReadError::Read(error))));
+                },
+                Poll::Pending => return Poll::Pending,
+            };
+            if buf.is_empty() {
+                *this.done = true;
+                // EOF between values is a clean end, otherwise the decoder reports the error.
+                if *this.in_progress {
+                    return Poll::Ready(Some(this.decoder.take().map_err(|error| ReadError::Decode(SequenceError::Decode(error)))));
+                }
+                break;
+            }
+            let buf_len = buf.len();
+            let num = match this.decoder.bytes_received(buf) {
+                Ok(num) => num,
+                Err(error) => {
+                    *this.done = true;
+                    return Poll::Ready(Some(Err(ReadError::Decode(SequenceError::Decode(error)))));
+                },
+            };
+            (this.consume_fn)(this.reader.as_mut(), num);
+            *this.in_progress |= num > 0;
+            if num < buf_len {
+                let result = if *this.in_progress {
+                    this.decoder.take().map_err(SequenceError::Decode)
+                } else {
+                    // An empty value doesn't consume anything so yielding it would never end.
+                    Err(SequenceError::NoProgress)
+                };
+                *this.in_progress = false;
+                *this.done = result.is_err();
+                return Poll::Ready(Some(result.map_err(ReadError::Decode)));
+            }
+        }
+        Poll::Ready(None)
+    }
+}
+
 pin_project! {
     pub(crate) struct EncodeFuture<T, PollWriteFn, E: Encoder> {
         #[pin]
@@ -122,18 +188,153 @@ impl<T, PollWriteFn, E> Future for EncodeFuture<T, PollWriteFn, E> where
     fn poll(self: Pin<&mut Self>, ctx: &mut Context) -> Poll<Self::Output> {
         let mut this = self.project();
 
-        while !this.encoder.encoded_chunk().is_empty() {
-            match PollWriteFn::poll_write(this.writer.as_mut(), ctx, this.encoder.encoded_chunk()) {
+        loop {
+            let mut chunks = [&[][..]; crate::MAX_VECTORED_CHUNKS];
+            let count = this.encoder.encoded_chunks(&mut chunks);
+            if count == 0 {
+                break Poll::Ready(Ok(()));
+            }
+            let mut slices = [io::IoSlice::new(&[]); crate::MAX_VECTORED_CHUNKS];
+            for (slice, chunk) in slices.iter_mut().zip(&chunks[..count]) {
+                *slice = io::IoSlice::new(chunk);
+            }
+            match PollWriteFn::poll_write_vectored(this.writer.as_mut(), ctx, &slices[..count]) {
+                Poll::Ready(Ok(0)) => break Poll::Ready(Err(io::ErrorKind::WriteZero.into())),
                 Poll::Ready(Ok(amount)) => this.encoder.consume(amount),
-                Poll::Ready(Err(error)) => return Poll::Ready(Err(error)),
-                Poll::Pending => return Poll::Pending,
+                Poll::Ready(Err(error)) => break Poll::Ready(Err(error)),
+                Poll::Pending => break Poll::Pending,
             }
         }
-        Poll::Ready(Ok(()))
     }
 }
 
 // Can't use FnMut because fns are unnameable.
 trait PollWrite<Writer> {
-    fn poll_write(writer: Pin<&mut Writer>, ctx: &mut Context, bytes: &[u8]) -> Poll<io::Result<usize>>;
+    fn poll_write_vectored(writer: Pin<&mut Writer>, ctx: &mut Context, bufs: &[io::IoSlice]) -> Poll<io::Result<usize>>;
+}
+
+#[cfg(any(feature = "futures_0_3", feature = "tokio"))]
+#[cfg(test)]
+mod tests {
+    use alloc::boxed::Box;
+    use std::pin::Pin;
+    use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+    use futures_core_0_3::Stream;
+    use crate::decoders::{ByteArrayDecoder, U8Decoder};
+    use crate::error::SequenceError;
+    use crate::ReadError;
+
+    // The readers used in tests never return `Pending` so the waker is never used.
+    fn noop_waker() -> Waker {
+        fn clone(_: *const ()) -> RawWaker {
+            RawWaker::new(core::ptr::null(), &VTABLE)
+        }
+        fn noop(_: *const ()) {}
+        static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+
+        unsafe { Waker::from_raw(clone(core::ptr::null())) }
+    }
+
+    fn next<S: Stream + Unpin>(stream: &mut S) -> Option<S::Item> {
+        let waker = noop_waker();
+        match Pin::new(stream).poll_next(&mut Context::from_waker(&waker)) {
+            Poll::Ready(item) => item,
+            Poll::Pending => panic!("the stream is pending"),
+        }
+    }
+
+    #[cfg(feature = "futures_0_3")]
+    #[test]
+    fn clean_end() {
+        let mut stream = Box::pin(crate::decode_stream_futures_0_3::<U8Decoder, _>(&[1u8, 2][..]));
+        assert_eq!(next(&mut stream).unwrap().unwrap(), 1);
+        assert_eq!(next(&mut stream).unwrap().unwrap(), 2);
+        assert!(next(&mut stream).is_none());
+        assert!(next(&mut stream).is_none());
+    }
+
+    #[cfg(feature = "futures_0_3")]
+    #[test]
+    fn unexpected_end() {
+        type U16Decoder = crate::decoders::IntDecoder<u16, crate::int::BigEndian>;
+
+        let mut stream = Box::pin(crate::decode_stream_futures_0_3::<U16Decoder, _>(&[0u8, 1, 2][..]));
+        assert_eq!(next(&mut stream).unwrap().unwrap(), 1);
+        assert!(matches!(next(&mut stream), Some(Err(ReadError::Decode(_)))));
+        assert!(next(&mut stream).is_none());
+    }
+
+    #[cfg(feature = "futures_0_3")]
+    #[test]
+    fn empty_values() {
+        let mut stream = Box::pin(crate::decode_stream_futures_0_3::<ByteArrayDecoder<0>, _>(&[1u8, 2][..]));
+        assert!(matches!(next(&mut stream), Some(Err(ReadError::Decode(SequenceError::NoProgress)))));
+        assert!(next(&mut stream).is_none());
+
+        let mut stream = Box::pin(crate::decode_stream_futures_0_3::<ByteArrayDecoder<0>, _>(&[][..]));
+        assert!(next(&mut stream).is_none());
+    }
+
+    #[cfg(feature = "tokio")]
+    #[test]
+    fn tokio_assume_buffered_vectored() {
+        use std::future::Future;
+        use std::io;
+        use crate::Encoder;
+        use crate::encoders::BytesEncoder;
+
+        struct Writer {
+            slices: alloc::vec::Vec<usize>,
+        }
+
+        impl actual_tokio::io::AsyncWrite for Writer {
+            fn poll_write(self: Pin<&mut Self>, _: &mut Context, _: &[u8]) -> Poll<io::Result<usize>> {
+                panic!("non-vectored write");
+            }
+
+            fn poll_write_vectored(mut self: Pin<&mut Self>, _: &mut Context, bufs: &[io::IoSlice]) -> Poll<io::Result<usize>> {
+                self.slices.push(bufs.len());
+                Poll::Ready(Ok(bufs.iter().map(|buf| buf.len()).sum()))
+            }
+
+            fn is_write_vectored(&self) -> bool {
+                true
+            }
+
+            fn poll_flush(self: Pin<&mut Self>, _: &mut Context) -> Poll<io::Result<()>> {
+                Poll::Ready(Ok(()))
+            }
+
+            fn poll_shutdown(self: Pin<&mut Self>, _: &mut Context) -> Poll<io::Result<()>> {
+                Poll::Ready(Ok(()))
+            }
+        }
+
+        let mut writer = crate::AssumeBuffered::new(Writer { slices: alloc::vec::Vec::new() });
+        assert!(actual_tokio::io::AsyncWrite::is_write_vectored(&writer));
+        let encoder = BytesEncoder::new([1, 2]).chain(BytesEncoder::new([3, 4])).chain(BytesEncoder::new([5]));
+        let mut future = Box::pin(encoder.write_all_tokio(&mut writer));
+        let waker = noop_waker();
+        assert!(matches!(future.as_mut().poll(&mut Context::from_waker(&waker)), Poll::Ready(Ok(()))));
+        drop(future);
+        assert_eq!(writer.inner().slices, [3]);
+    }
+
+    #[cfg(feature = "tokio")]
+    #[test]
+    fn tokio() {
+        let mut stream = Box::pin(crate::decode_stream_tokio::<U8Decoder, _>(&[1u8, 2][..]));
+        assert_eq!(next(&mut stream).unwrap().unwrap(), 1);
+        assert_eq!(next(&mut stream).unwrap().unwrap(), 2);
+        assert!(next(&mut stream).is_none());
+
+        let mut stream = Box::pin(crate::decode_stream_tokio::<crate::decoders::U16BeDecoder, _>(&[0u8, 1, 2][..]));
+        assert_eq!(next(&mut stream).unwrap().unwrap(), 1);
+        assert!(matches!(next(&mut stream), Some(Err(ReadError::Decode(_)))));
+        assert!(next(&mut stream).is_none());
+
+        let mut stream = Box::pin(crate::decode_stream_tokio::<ByteArrayDecoder<0>, _>(&[1u8, 2][..]));
+        assert!(matches!(next(&mut stream), Some(Err(ReadError::Decode(SequenceError::NoProgress)))));
+        assert!(next(&mut stream).is_none());
+    }
 }
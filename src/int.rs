@@ -1,4 +1,4 @@
-//! Tools for decoding and encoding integers.
+//! Tools for decoding and encoding integers and floating-point numbers.
 
 use crate::{Decoder, KnownMinLenDecoder, decoders::ByteArrayDecoder};
 
@@ -16,9 +16,55 @@ pub trait Int: sealed::Int {
     fn to_le_bytes(self) -> Self::Bytes;
     #[doc(hidden)]
     fn to_be_bytes(self) -> Self::Bytes;
+    #[doc(hidden)]
+    const BITS: u32;
 }
 
-pub trait ByteOrder: sealed::ByteOrder {}
+/// Unsigned integers.
+pub trait Unsigned: Int {
+    #[doc(hidden)]
+    fn from_u128(value: u128) -> Self;
+    #[doc(hidden)]
+    fn into_u128(self) -> u128;
+}
+
+/// Signed integers.
+pub trait Signed: Int {
+    #[doc(hidden)]
+    fn from_i128(value: i128) -> Self;
+    #[doc(hidden)]
+    fn into_i128(self) -> i128;
+}
+
+/// Floating-point numbers.
+///
+/// These are encoded as their IEEE 754 bit representation stored in an unsigned integer.
+pub trait Float: sealed::Float + Copy {
+    #[doc(hidden)]
+    type Bits: Unsigned;
+
+    #[doc(hidden)]
+    fn from_bits(bits: Self::Bits) -> Self;
+    #[doc(hidden)]
+    fn to_bits(self) -> Self::Bits;
+    #[doc(hidden)]
+    fn is_nan(self) -> bool;
+    #[doc(hidden)]
+    const CANONICAL_NAN_BITS: Self::Bits;
+}
+
+/// The order of bytes of encoded integers.
+pub trait ByteOrder: sealed::ByteOrder {
+    #[doc(hidden)]
+    fn from_bytes<T: Int>(bytes: T::Bytes) -> T;
+    #[doc(hidden)]
+    fn to_bytes<T: Int>(value: T) -> T::Bytes;
+    #[doc(hidden)]
+    fn uint_from_bytes(bytes: &[u8]) -> u128;
+    /// Writes the lowest `bytes.len()` bytes of `value` into `bytes`.
+    #[doc(hidden)]
+    fn uint_to_bytes(value: u128, bytes: &mut [u8]);
+}
 
 macro_rules! impl_int {
     ($($int:ty),+) => {
@@ -42,6 +88,8 @@ macro_rules! impl_int {
                 fn to_be_bytes(self) -> Self::Bytes {
                     <$int>::to_be_bytes(self)
                 }
+
+                const BITS: u32 = <$int>::BITS;
             }
 
             impl sealed::Int for $int {}
@@ -51,15 +99,133 @@ macro_rules! impl_int {
 
 impl_int!(u8, i8, u16, i16, u32, i32, u64, i64, u128, i128);
 
+macro_rules! impl_unsigned {
+    ($($int:ty),+) => {
+        $(
+            impl Unsigned for $int {
+                // truncation is intended, callers check the range
+                fn from_u128(value: u128) -> Self {
+                    value as $int
+                }
+
+                fn into_u128(self) -> u128 {
+                    self.into()
+                }
+            }
+        )+
+    }
+}
+
+macro_rules! impl_signed {
+    ($($int:ty),+) => {
+        $(
+            impl Signed for $int {
+                // truncation is intended, callers check the range
+                fn from_i128(value: i128) -> Self {
+                    value as $int
+                }
+
+                fn into_i128(self) -> i128 {
+                    self.into()
+                }
+            }
+        )+
+    }
+}
+
+impl_unsigned!(u8, u16, u32, u64, u128);
+impl_signed!(i8, i16, i32, i64, i128);
+
+macro_rules! impl_float {
+    ($($float:ty => $bits:ty, $nan:expr);+ $(;)?) => {
+        $(
+            impl Float for $float {
+                type Bits = $bits;
+
+                fn from_bits(bits: Self::Bits) -> Self {
+                    <$float>::from_bits(bits)
+                }
+
+                fn to_bits(self) -> Self::Bits {
+                    <$float>::to_bits(self)
+                }
+
+                fn is_nan(self) -> bool {
+                    <$float>::is_nan(self)
+                }
+
+                const CANONICAL_NAN_BITS: Self::Bits = $nan;
+            }
+
+            impl sealed::Float for $float {}
+        )+
+    }
+}
+
+// The quiet NaN with zero payload and positive sign, same as `f32::NAN` and `f64::NAN`.
+impl_float! {
+    f32 => u32, 0x7FC0_0000;
+    f64 => u64, 0x7FF8_0000_0000_0000;
+}
+
 mod sealed {
     pub trait Int {}
+    pub trait Float {}
     pub trait ByteOrder {}
 }
 
+/// The most significant byte is first.
 pub struct BigEndian {}
+
+/// The least significant byte is first.
 pub struct LittleEndian {}
 
-impl ByteOrder for BigEndian {}
+/// The byte order of the target platform.
+#[cfg(target_endian = "big")]
+pub type NativeEndian = BigEndian;
+
+/// The byte order of the target platform.
+#[cfg(target_endian = "little")]
+pub type NativeEndian = LittleEndian;
+
+impl ByteOrder for BigEndian {
+    fn from_bytes<T: Int>(bytes: T::Bytes) -> T {
+        T::from_be_bytes(bytes)
+    }
+
+    fn to_bytes<T: Int>(value: T) -> T::Bytes {
+        value.to_be_bytes()
+    }
+
+    fn uint_from_bytes(bytes: &[u8]) -> u128 {
+        bytes.iter().fold(0, |acc, byte| acc << 8 | u128::from(*byte))
+    }
+
+    fn uint_to_bytes(value: u128, bytes: &mut [u8]) {
+        let len = bytes.len();
+        bytes.copy_from_slice(&value.to_be_bytes()[(16 - len)..]);
+    }
+}
+
 impl sealed::ByteOrder for BigEndian {}
-impl ByteOrder for LittleEndian {}
+
+impl ByteOrder for LittleEndian {
+    fn from_bytes<T: Int>(bytes: T::Bytes) -> T {
+        T::from_le_bytes(bytes)
+    }
+
+    fn to_bytes<T: Int>(value: T) -> T::Bytes {
+        value.to_le_bytes()
+    }
+
+    fn uint_from_bytes(bytes: &[u8]) -> u128 {
+        bytes.iter().rev().fold(0, |acc, byte| acc << 8 | u128::from(*byte))
+    }
+
+    fn uint_to_bytes(value: u128, bytes: &mut [u8]) {
+        let len = bytes.len();
+        bytes.copy_from_slice(&value.to_le_bytes()[..len]);
+    }
+}
+
 impl sealed::ByteOrder for LittleEndian {}
@@ -13,10 +13,12 @@
 //!
 //! * `std` - enables integration with the standard library - it's IO and error traits
 //! * `alloc` - enables integration with the standard `alloc` crate
+//! * `derive` - provides `#[derive(Decode, Encode)]` macros, see [`Decode`](macro@Decode).
 //! * `lgio` - connects decoders to lgio IO.
 //! * `tokio` - connects decoders to Tokio IO.
 //! * `async-std` - connects decoders to async-std IO.
 //! * `futures_0_3` - connects decoders to futures 0.3.x IO
+//! * `tokio-util` - provides a codec for `tokio_util::codec`
 
 #![no_std]
 #![cfg_attr(docsrs, feature(doc_auto_cfg))]
@@ -29,7 +31,7 @@ extern crate std;
 #[cfg_attr(test, macro_use)]
 extern crate alloc;
 
-#[cfg(any(feature = "tokio", feature = "async-std", feature = "futures_0_3"))]
+#[cfg(any(feature = "tokio", feature = "async-std"))]
 use core::pin::Pin;
 
 #[cfg(feature = "tokio")]
@@ -37,6 +39,75 @@ use actual_tokio as tokio;
 #[cfg(feature = "async-std")]
 use actual_async_std as async_std;
 
+/// Derives [`Decode`](trait@Decode) for a struct or an enum.
+///
+/// The fields are decoded one after another in the order of declaration using their
+/// [`Decode::Decoder`]. The macro generates a decoder named `<Type>Decoder` and an error named
+/// `<Type>DecodeError` having one variant per field so that the failing field can be identified.
+/// Both have the same visibility as the type. The decoder implements [`KnownMinLenDecoder`] if
+/// the decoders of all fields implement it and the error implements `std::error::Error` if the
+/// `std` feature is enabled.
+///
+/// Enums are encoded as a tag followed by the fields of the variant. The tag is a big-endian
+/// integer, `u8` by default, this can be changed with `#[push_decode(tag_type = "u16")]` on the
+/// enum. The tag of each
+/// variant is its index unless it has an explicit discriminant or `#[push_decode(tag = 42)]`
+/// attribute. The error has additional `Tag` and `UnknownTag` variants.
+///
+/// The fields support these attributes:
+///
+/// * `#[push_decode(endian = "le")]` - decodes an integer as little endian (`"be"` is default)
+/// * `#[push_decode(len = "field")]` - decodes `Vec<u8>` or `String` with the length being
+///   the value of a previous integer `field`. Note that this requires the `alloc` feature. The
+///   encoder ignores the value of `field` and encodes the actual length instead so each `field`
+///   can store the length of only one field.
+///
+/// Generic types are not supported.
+///
+/// ```
+/// use push_decode::{Decode, Decoder, Encode, Encoder};
+///
+/// #[derive(Decode, Encode)]
+/// struct Message {
+///     version: u8,
+///     #[push_decode(endian = "le")]
+///     flags: u32,
+///     nonce: [u8; 4],
+/// }
+///
+/// let mut decoder = MessageDecoder::new();
+/// decoder.bytes_received(&[1, 2, 0, 0, 0, 3, 4, 5, 6]).unwrap();
+/// let message = decoder.end().unwrap();
+/// assert_eq!(message.flags, 2);
+///
+/// let mut bytes = Vec::new();
+/// message.encoder().write_to_vec(&mut bytes);
+/// assert_eq!(bytes, [1, 2, 0, 0, 0, 3, 4, 5, 6]);
+/// ```
+#[cfg(feature = "derive")]
+pub use push_decode_derive::Decode;
+
+/// Derives [`Encode`](trait@Encode) for references to a struct or an enum.
+///
+/// The macro generates an encoder named `<Type>Encoder` with the same visibility as the type.
+/// It uses the same attributes and produces the same format as [`Decode`](macro@Decode).
+///
+/// If the type has `len` fields the length may not fit into the length field so `Encode` is not
+/// implemented and `<Type>Encoder::new` returns [`LengthOverflow`](error::LengthOverflow)
+/// instead.
+#[cfg(feature = "derive")]
+pub use push_decode_derive::Encode;
+
+pub mod bits;
+pub mod checksum;
+#[cfg(feature = "tokio-util")]
+pub mod codec;
+#[cfg(feature = "std")]
+mod decoder_writer;
+#[cfg(feature = "alloc")]
+mod dynamic;
+#[cfg(feature = "std")]
+mod encoder_reader;
 pub mod decoders;
 pub mod encoders;
 pub mod error;
@@ -44,6 +115,15 @@ pub mod int;
 #[cfg(any(feature = "tokio", feature = "async-std", feature = "futures_0_3"))]
 pub mod future;
 mod macros;
+#[cfg(test)]
+mod test_utils;
+
+// Used by the macros, not a public API.
+#[doc(hidden)]
+pub mod __private {
+    #[cfg(feature = "std")]
+    pub use std::error::Error as StdError;
+}
 
 use core::fmt;
 use core::ops::ControlFlow;
@@ -117,6 +197,40 @@ pub trait Decoder: Sized {
         decoders::combinators::Chain::new(self, following)
     }
 
+    /// Transforms the decoded value using a function.
+    fn map<T, F: FnOnce(Self::Value) -> T>(self, fun: F) -> decoders::combinators::Map<Self, F, T> {
+        decoders::combinators::Map::new(self, fun)
+    }
+
+    /// Transforms the decoding error using a function.
+    fn map_err<E, F: FnMut(Self::Error) -> E>(self, fun: F) -> decoders::combinators::MapErr<Self, F, E> {
+        decoders::combinators::MapErr::new(self, fun)
+    }
+
+    /// Transforms the decoded value using a fallible function.
+    ///
+    /// The errors of this decoder are converted into the error returned by the function.
+    fn try_map<E, T, F: FnOnce(Self::Value) -> Result<T, E>>(self, fun: F) -> decoders::combinators::TryMap<E, Self, F, T> where E: From<Self::Error> {
+        decoders::combinators::TryMap::new(self, fun)
+    }
+
+    /// Limits the decoder to exactly `limit` bytes.
+    ///
+    /// This is useful for decoding length-delimited sub-messages. The inner decoder only sees the
+    /// bytes up to the limit and it's an error if it finishes before consuming all of them or if
+    /// it doesn't finish when the limit is reached.
+    fn limit(self, limit: usize) -> decoders::combinators::Limit<Self> {
+        decoders::combinators::Limit::new(self, limit)
+    }
+
+    /// Verifies the checksum following the decoded value.
+    ///
+    /// All bytes consumed by this decoder are fed into `checksum`. Once this decoder finishes the
+    /// digest is decoded and compared with the computed one.
+    fn verify_checksum<C: checksum::Checksum>(self, checksum: C) -> decoders::combinators::VerifyChecksum<Self, C> {
+        decoders::combinators::VerifyChecksum::new(self, checksum)
+    }
+
     /// Resets the decoder returning the decoded value.
     fn take(&mut self) -> Result<Self::Value, Self::Error> where Self: Default {
         core::mem::take(self).end()
@@ -269,6 +383,43 @@ pub trait Encoder: Sized {
     #[must_use = "Relying on encoded_chunk being empty is insufficient"]
     fn next(&mut self) -> bool;
 
+    /// Returns the number of bytes remaining to be encoded if it's known.
+    ///
+    /// The number includes the current chunk and all the following chunks. If this returns
+    /// `Some` the value MUST be exact. The returned value is unspecified after
+    /// [`next()`](Self::next) returned `false`.
+    ///
+    /// The default implementation returns `None`. Encoders that always know their length should
+    /// implement [`ExactSizeEncoder`] as well.
+    fn encoded_len_hint(&self) -> Option<usize> {
+        None
+    }
+
+    /// Collects the current and upcoming chunks without advancing the encoder.
+    ///
+    /// Stores up to `chunks.len()` chunks into `chunks` starting with the current chunk and
+    /// returns the number of stored chunks together with a flag which is `true` if the stored
+    /// chunks are all chunks remaining in this encoder. The stored chunks MUST be exactly the
+    /// chunks that would be returned by [`encoded_chunk`](Self::encoded_chunk) after calling
+    /// [`next()`](Self::next) and they MUST NOT be empty.
+    ///
+    /// This allows consumers to write multiple chunks at once using vectored writes. The default
+    /// implementation only stores the current chunk and returns `false` since it doesn't know
+    /// whether there are more chunks.
+    fn lookahead<'a>(&'a self, chunks: &mut [&'a [u8]]) -> (usize, bool) {
+        let chunk = self.encoded_chunk();
+        if chunk.is_empty() {
+            return (0, true);
+        }
+        match chunks.first_mut() {
+            Some(first) => {
+                *first = chunk;
+                (1, false)
+            },
+            None => (0, false),
+        }
+    }
+
     /// Returns a wrapper that tracks the position of processed bytes.
     ///
     /// The returned wrapper has a bit different interface that is more suitable for writing into
@@ -321,10 +472,14 @@ pub trait Encoder: Sized {
 
     /// Writes all encoded bytes to a vec.
     ///
-    /// Note that this does **not** call `reserve` since there's no way to know the amount to
-    /// reserve. This should be handled by the code producing the decoder instead.
+    /// This reserves the required capacity if the length is known from
+    /// [`encoded_len_hint`](Self::encoded_len_hint). Otherwise it should be handled by the code
+    /// producing the encoder.
     #[cfg(feature = "alloc")]
     fn write_to_vec(self, buf: &mut alloc::vec::Vec<u8>) {
+        if let Some(len) = self.encoded_len_hint() {
+            buf.reserve(len);
+        }
         self.for_each_sync(|chunk| buf.extend_from_slice(chunk));
     }
 
@@ -334,15 +489,32 @@ pub trait Encoder: Sized {
         self.try_for_each_sync(|chunk| writer.write_all(chunk))
     }
 
+    /// Writes all encoded bytes to the `std` writer using vectored writes.
+    ///
+    /// Multiple chunks obtained using [`lookahead`](Self::lookahead) are written at once which
+    /// makes this suitable for unbuffered writers that support vectored writes, such as sockets.
+    #[cfg(feature = "std")]
+    fn write_all_vectored_sync<W: std::io::Write>(self, mut writer: W) -> std::io::Result<()> {
+        let mut encoder = self.track_position();
+        loop {
+            match encoder.write_all_vectored(&mut writer) {
+                Err(error) if error.kind() == std::io::ErrorKind::Interrupted => (),
+                result => break result,
+            }
+        }
+    }
+
     /// Writes all encoded bytes to the `std` writer.
     #[cfg(feature = "lgio")]
-    fn write_all_sync_lgio<W: lgio::BufWrite>(mut self, mut writer: W) -> Result<(), W::WriteError> {
+    fn write_all_sync_lgio<W: lgio::BufWrite>(self, mut writer: W) -> Result<(), W::WriteError> {
         self.try_for_each_sync(|chunk| writer.write_all(chunk))
     }
 
     /// Writes all encoded bytes to the `tokio` async writer.
     ///
     /// The returned future resolves to `std::io::Result<()>`.
+    /// The bytes are written using vectored writes so wrapping an unbuffered writer that supports
+    /// them in [`AssumeBuffered`] is viable.
     #[cfg(feature = "tokio")]
     fn write_all_tokio<W: tokio::io::AsyncWrite + BufWrite>(self, writer: W) -> future::TokioEncodeFuture<W, Self> {
         future::TokioEncodeFuture::new(writer, self)
@@ -351,6 +523,8 @@ pub trait Encoder: Sized {
     /// Writes all encoded bytes to the `async-std` async writer.
     ///
     /// The returned future resolves to `std::io::Result<()>`.
+    /// The bytes are written using vectored writes so wrapping an unbuffered writer that supports
+    /// them in [`AssumeBuffered`] is viable.
     #[cfg(feature = "async-std")]
     fn write_all_async_std<W: async_std::io::Write + BufWrite>(self, writer: W) -> future::AsyncStdEncodeFuture<W, Self> {
         future::AsyncStdEncodeFuture::new(writer, self)
@@ -359,6 +533,8 @@ pub trait Encoder: Sized {
     /// Writes all encoded bytes to the `futures` 0.3 async writer.
     ///
     /// The returned future resolves to `std::io::Result<()>`.
+    /// The bytes are written using vectored writes so wrapping an unbuffered writer that supports
+    /// them in [`AssumeBuffered`] is viable.
     #[cfg(feature = "futures_0_3")]
     fn write_all_futures_0_3<W: futures_io_0_3::AsyncWrite + BufWrite>(self, writer: W) -> future::Futures0Dot3EncodeFuture<W, Self> {
         future::Futures0Dot3EncodeFuture::new(writer, self)
@@ -385,6 +561,61 @@ pub trait Encoder: Sized {
     fn chain<T: Encoder>(self, second_encoder: T) -> encoders::combinators::Chain<Self, T> {
         encoders::combinators::Chain::new(self, second_encoder)
     }
+
+    /// Appends the checksum of all encoded bytes after them.
+    ///
+    /// Each chunk is fed into `checksum` as it becomes current and the digest is returned as the
+    /// last chunk so no buffering is needed.
+    fn append_checksum<C: checksum::Checksum>(self, checksum: C) -> encoders::combinators::AppendChecksum<Self, C> {
+        encoders::combinators::AppendChecksum::new(self, checksum)
+    }
+}
+
+/// Represents encoders that always know the number of remaining bytes.
+///
+/// This is useful to emit length prefixes without encoding the value twice.
+pub trait ExactSizeEncoder: Encoder {
+    /// Returns the number of bytes remaining to be encoded.
+    ///
+    /// The number includes the current chunk and all the following chunks. The implementors
+    /// MUST return the exact value and the same value from
+    /// [`encoded_len_hint`](Encoder::encoded_len_hint). The returned value is unspecified after
+    /// [`next()`](Encoder::next) returned `false`.
+    fn encoded_len(&self) -> usize;
+}
+
+/// Types that have a canonical decoder.
+///
+/// This allows generic code to decode values without naming the decoder type. The formats used by
+/// the implementations in this crate are:
+///
+/// * integers are big-endian
+/// * byte arrays are encoded as-is
+/// * `String` and `Vec<T>` are prefixed by their length encoded as LEB128
+/// * `Option<T>` is prefixed by a tag byte: `0` for `None` and `1` for `Some`
+/// * tuples are encoded as their elements one after another
+///
+/// The [`Encode`] implementations use the same formats.
+pub trait Decode: Sized {
+    /// The decoder producing this type.
+    type Decoder: Decoder<Value = Self> + Default;
+}
+
+/// Types that have a canonical encoder.
+///
+/// Similarly to [`IntoIterator`] this is usually implemented for references so that the encoder
+/// can borrow the value instead of copying it. Generic code should use `for<'a> &'a T: Encode`
+/// bounds.
+///
+/// Note that the implementations for containers such as `&Vec<T>` are recursive, so the compiler
+/// may fail to infer `T` when calling functions with such bounds. Specifying `T` explicitly or
+/// accepting `impl Encode` instead (and passing a reference) avoids the problem.
+pub trait Encode {
+    /// The encoder returned by [`encoder`](Self::encoder).
+    type Encoder: Encoder;
+
+    /// Creates the encoder for the value.
+    fn encoder(self) -> Self::Encoder;
 }
 
 /// Marker trait for writers that are either buffered or don't incur the cost of context switch.
@@ -395,7 +626,7 @@ pub trait Encoder: Sized {
 pub trait BufWrite {}
 
 #[cfg(any(feature = "std", feature = "tokio", feature = "async-std", feature = "futures_0_3"))]
-impl<'a, T: BufWrite> BufWrite for &mut T {}
+impl<T: BufWrite> BufWrite for &mut T {}
 
 #[cfg(feature = "std")]
 impl<T: std::io::Write> BufWrite for std::io::BufWriter<T> {}
@@ -457,6 +688,10 @@ impl<T: std::io::Write> std::io::Write for AssumeBuffered<T> {
         self.inner.write(bytes)
     }
 
+    fn write_vectored(&mut self, bufs: &[std::io::IoSlice]) -> std::io::Result<usize> {
+        self.inner.write_vectored(bufs)
+    }
+
     fn flush(&mut self) -> std::io::Result<()> {
         self.inner.flush()
     }
@@ -468,6 +703,10 @@ impl<T: async_std::io::Write> async_std::io::Write for AssumeBuffered<T> {
         self.project().inner.poll_write(ctx, bytes)
     }
 
+    fn poll_write_vectored(self: Pin<&mut Self>, ctx: &mut core::task::Context, bufs: &[std::io::IoSlice]) -> core::task::Poll<std::io::Result<usize>> {
+        self.project().inner.poll_write_vectored(ctx, bufs)
+    }
+
     fn poll_flush(self: Pin<&mut Self>, ctx: &mut core::task::Context) -> core::task::Poll<std::io::Result<()>> {
         self.project().inner.poll_flush(ctx)
     }
@@ -483,6 +722,14 @@ impl<T: tokio::io::AsyncWrite> tokio::io::AsyncWrite for AssumeBuffered<T> {
         self.project().inner.poll_write(ctx, bytes)
     }
 
+    fn poll_write_vectored(self: Pin<&mut Self>, ctx: &mut core::task::Context, bufs: &[std::io::IoSlice]) -> core::task::Poll<std::io::Result<usize>> {
+        self.project().inner.poll_write_vectored(ctx, bufs)
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.inner.is_write_vectored()
+    }
+
     fn poll_flush(self: Pin<&mut Self>, ctx: &mut core::task::Context) -> core::task::Poll<std::io::Result<()>> {
         self.project().inner.poll_flush(ctx)
     }
@@ -492,6 +739,10 @@ impl<T: tokio::io::AsyncWrite> tokio::io::AsyncWrite for AssumeBuffered<T> {
     }
 }
 
+/// The maximum number of chunks written by a single vectored write.
+#[cfg(any(feature = "std", feature = "tokio", feature = "async-std", feature = "futures_0_3"))]
+const MAX_VECTORED_CHUNKS: usize = 16;
+
 /// An `Encoder` wrapper that handles partial writes.
 ///
 /// This wrapper internally tracks the position of encoded bytes which makes handling of partial
@@ -527,21 +778,51 @@ impl<Enc: Encoder> EncoderPositionTracker<Enc> {
     /// bytes and, if it reached the end, the underlying encoder will be advanced to give the next
     /// chunk.
     ///
-    /// Calling this method with `amount` larger than `encoded_chunk().len()` will corrupt the
-    /// encoder and lead to a panic later. In debug builds this will panic instantly.
+    /// The `amount` may be larger than the length of the current chunk if the bytes were
+    /// obtained using [`encoded_chunks`](Self::encoded_chunks). Calling this method with `amount`
+    /// larger than the total length of the remaining chunks will corrupt the encoder and lead to a
+    /// panic later. In debug builds this will panic instantly.
     #[track_caller]
-    pub fn consume(&mut self, amount: usize) {
-        self.pos += amount;
-        if self.pos >= self.encoder.encoded_chunk().len() {
-            debug_assert_eq!(self.pos, self.encoder.encoded_chunk().len());
+    pub fn consume(&mut self, mut amount: usize) {
+        loop {
+            let remaining = self.encoder.encoded_chunk().len() - self.pos;
+            if amount < remaining {
+                self.pos += amount;
+                break;
+            }
+            amount -= remaining;
             // Resetting only position when there are more chunks ensures `encoded_bytes` will be
             // empty when this reaches the end.
             if self.encoder.next() {
                 self.pos = 0;
+            } else {
+                self.pos += remaining;
+                debug_assert_eq!(amount, 0);
+                break;
+            }
+            if amount == 0 {
+                break;
             }
         }
     }
 
+    /// Returns unprocessed chunks of encoded data.
+    ///
+    /// This is the same as [`encoded_chunk`](Self::encoded_chunk) except it stores multiple chunks
+    /// using [`Encoder::lookahead`]. Returns the number of stored chunks, zero indicates there are
+    /// no more bytes (unless `chunks` is empty).
+    pub fn encoded_chunks<'a>(&'a self, chunks: &mut [&'a [u8]]) -> usize {
+        // The encoder may return stale chunk after it ended.
+        if self.encoded_chunk().is_empty() {
+            return 0;
+        }
+        let (count, _) = self.encoder.lookahead(chunks);
+        if let Some(first) = chunks[..count].first_mut() {
+            *first = &first[self.pos..];
+        }
+        count
+    }
+
     /// Issues single write to te writer and advances the position accordingly.
     ///
     /// This can be used as a building block for various abstractions or protocols.
@@ -568,8 +849,50 @@ impl<Enc: Encoder> EncoderPositionTracker<Enc> {
         while self.write_once(writer)? != 0 { }
         Ok(())
     }
+
+    /// Issues single vectored write to the writer and advances the position accordingly.
+    ///
+    /// This is the same as [`write_once`](Self::write_once) except it may write multiple chunks
+    /// at once.
+    #[cfg(feature = "std")]
+    pub fn write_vectored_once<W: std::io::Write>(&mut self, writer: &mut W) -> std::io::Result<usize> {
+        let mut chunks = [&[][..]; MAX_VECTORED_CHUNKS];
+        let count = self.encoded_chunks(&mut chunks);
+        if count == 0 {
+            return Ok(0);
+        }
+        let mut slices = [std::io::IoSlice::new(&[]); MAX_VECTORED_CHUNKS];
+        for (slice, chunk) in slices.iter_mut().zip(&chunks[..count]) {
+            *slice = std::io::IoSlice::new(chunk);
+        }
+        let amount = writer.write_vectored(&slices[..count])?;
+        self.consume(amount);
+        Ok(amount)
+    }
+
+    /// Writes all bytes to the writer using vectored writes until the end or an error.
+    ///
+    /// This is the same as [`write_all`](Self::write_all) except it may write multiple chunks at
+    /// once and it returns [`WriteZero`](std::io::ErrorKind::WriteZero) error if the writer
+    /// doesn't accept any bytes.
+    #[cfg(feature = "std")]
+    pub fn write_all_vectored<W: std::io::Write>(&mut self, writer: &mut W) -> std::io::Result<()> {
+        while self.write_vectored_once(writer)? != 0 { }
+        if self.encoded_chunk().is_empty() {
+            Ok(())
+        } else {
+            Err(std::io::ErrorKind::WriteZero.into())
+        }
+    }
 }
 
+#[cfg(feature = "std")]
+pub use decoder_writer::DecoderWriter;
+#[cfg(feature = "std")]
+pub use encoder_reader::EncoderReader;
+#[cfg(feature = "alloc")]
+pub use dynamic::{DynDecoder, DynEncoder};
+
 /// Synchronously decodes a value from the given reader using a custom decoder.
 #[cfg(feature = "std")]
 pub fn decode_sync_with<D: Decoder, R: std::io::BufRead + ?Sized>(reader: &mut R, mut decoder: D) -> Result<D::Value, ReadError<D::Error>> {
@@ -611,6 +934,131 @@ pub fn decode_sync<D: Decoder + Default>(reader: &mut (impl std::io::BufRead + ?
     decode_sync_with(reader, D::default())
 }
 
+/// Synchronously decodes a value of type `T` from the given reader.
+///
+/// This is a shorthand for [`decode_sync`] using the decoder from [`Decode`].
+///
+/// ```
+/// use push_decode::{Encode, Encoder};
+///
+/// let value = (42u16, Some(String::from("foo")));
+/// let mut bytes = Vec::new();
+/// (&value).encoder().write_to_vec(&mut bytes);
+/// let decoded = push_decode::decode_sync_value::<(u16, Option<String>)>(&mut &*bytes).unwrap();
+/// assert_eq!(decoded, value);
+/// ```
+#[cfg(feature = "std")]
+pub fn decode_sync_value<T: Decode>(reader: &mut (impl std::io::BufRead + ?Sized)) -> Result<T, ReadError<<T::Decoder as Decoder>::Error>> {
+    decode_sync_with(reader, T::Decoder::default())
+}
+
+/// Synchronously decodes values from the given reader until it reaches EOF.
+///
+/// After each decoded value the decoder is reset using `Default`. The iterator ends when the
+/// reader reaches EOF between values. If EOF is reached in the middle of a value the iterator
+/// returns the error from [`Decoder::end`]. If the decoder finishes a value without consuming
+/// any bytes the iterator returns [`SequenceError::NoProgress`](error::SequenceError::NoProgress)
+/// since empty values would be returned forever. The iterator ends after returning any error.
+///
+/// The decoder only learns that a value is complete when it rejects further bytes so a value
+/// ending exactly at the end of the currently available data is only returned once more bytes
+/// arrive or the reader reaches EOF.
+#[cfg(feature = "std")]
+pub fn decode_iter_sync<D: Decoder + Default, R: std::io::BufRead>(reader: R) -> DecodeIterSync<R, D> {
+    DecodeIterSync {
+        reader,
+        decoder: D::default(),
+        done: false,
+    }
+}
+
+/// Iterator returned by [`decode_iter_sync`].
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct DecodeIterSync<R, D> {
+    reader: R,
+    decoder: D,
+    done: bool,
+}
+
+#[cfg(feature = "std")]
+impl<R: std::io::BufRead, D: Decoder + Default> Iterator for DecodeIterSync<R, D> {
+    type Item = Result<D::Value, ReadError<error::SequenceError<D::Error>>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        decode_iter_next(
+            &mut self.reader,
+            &mut self.decoder,
+            &mut self.done,
+            <R as std::io::BufRead>::fill_buf,
+            <R as std::io::BufRead>::consume,
+            |error| error.kind() == std::io::ErrorKind::Interrupted,
+        )
+    }
+}
+
+/// The item returned by the synchronous iterators.
+#[cfg(any(feature = "std", feature = "lgio"))]
+type IterItem<D, E> = Result<<D as Decoder>::Value, ReadError<error::SequenceError<<D as Decoder>::Error>, E>>;
+
+/// Decodes the next value for the synchronous iterators.
+///
+/// The functions abstract over the reader traits, `is_interrupted` returns `true` for errors
+/// that should be retried.
+#[cfg(any(feature = "std", feature = "lgio"))]
+fn decode_iter_next<R, E, D: Decoder + Default>(
+    reader: &mut R,
+    decoder: &mut D,
+    done: &mut bool,
+    fill_buf: fn(&mut R) -> Result<&[u8], E>,
+    consume: fn(&mut R, usize),
+    is_interrupted: fn(&E) -> bool,
+) -> Option<IterItem<D, E>> {
+    use error::SequenceError;
+
+    // Set when the decoder received at least one byte of the current value.
+    let mut in_progress = false;
+    while !*done {
+        let buf = match fill_buf(reader) {
+            Ok(buf) => buf,
+            Err(error) if is_interrupted(&error) => continue,
+            Err(error) => {
+                *done = true;
+                return Some(Err(ReadError::Read(error)));
+            },
+        };
+        if buf.is_empty() {
+            *done = true;
+            // EOF between values is a clean end, otherwise the decoder reports the error.
+            if in_progress {
+                return Some(decoder.take().map_err(|error| ReadError::Decode(SequenceError::Decode(error))));
+            }
+            break;
+        }
+        let buf_len = buf.len();
+        let num = match decoder.bytes_received(buf) {
+            Ok(num) => num,
+            Err(error) => {
+                *done = true;
+                return Some(Err(ReadError::Decode(SequenceError::Decode(error))));
+            },
+        };
+        consume(reader, num);
+        in_progress |= num > 0;
+        if num < buf_len {
+            let result = if in_progress {
+                decoder.take().map_err(SequenceError::Decode)
+            } else {
+                // An empty value doesn't consume anything so returning it would never end.
+                Err(SequenceError::NoProgress)
+            };
+            *done = result.is_err();
+            return Some(result.map_err(ReadError::Decode));
+        }
+    }
+    None
+}
+
 /// Synchronously decodes a value from the given reader using a custom decoder.
 #[cfg(feature = "lgio")]
 pub fn decode_sync_lgio_with<D: Decoder, R: lgio::BufRead + ?Sized>(reader: &mut R, mut decoder: D) -> Result<D::Value, ReadError<D::Error, R::ReadError>> {
@@ -634,6 +1082,49 @@ pub fn decode_sync_lgio<D: Decoder + Default, R: lgio::BufRead + ?Sized>(reader:
     decode_sync_lgio_with(reader, D::default())
 }
 
+/// Synchronously decodes a value of type `T` from the given reader.
+#[cfg(feature = "lgio")]
+pub fn decode_sync_lgio_value<T: Decode, R: lgio::BufRead + ?Sized>(reader: &mut R) -> Result<T, ReadError<<T::Decoder as Decoder>::Error, R::ReadError>> {
+    decode_sync_lgio_with(reader, T::Decoder::default())
+}
+
+/// Synchronously decodes values from the given reader until it reaches EOF.
+///
+/// This behaves the same as [`decode_iter_sync`].
+#[cfg(feature = "lgio")]
+pub fn decode_iter_sync_lgio<D: Decoder + Default, R: lgio::BufRead>(reader: R) -> DecodeIterSyncLgio<R, D> {
+    DecodeIterSyncLgio {
+        reader,
+        decoder: D::default(),
+        done: false,
+    }
+}
+
+/// Iterator returned by [`decode_iter_sync_lgio`].
+#[cfg(feature = "lgio")]
+#[derive(Debug)]
+pub struct DecodeIterSyncLgio<R, D> {
+    reader: R,
+    decoder: D,
+    done: bool,
+}
+
+#[cfg(feature = "lgio")]
+impl<R: lgio::BufRead, D: Decoder + Default> Iterator for DecodeIterSyncLgio<R, D> {
+    type Item = Result<D::Value, ReadError<error::SequenceError<D::Error>, R::ReadError>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        decode_iter_next(
+            &mut self.reader,
+            &mut self.decoder,
+            &mut self.done,
+            <R as lgio::BufRead>::fill_buf,
+            <R as lgio::BufRead>::consume,
+            |_| false,
+        )
+    }
+}
+
 /// Asynchronously decodes a value from the given reader using a custom decoder.
 #[cfg(feature = "futures_0_3")]
 pub async fn decode_futures_0_3_with<D: Decoder, R: futures_io_0_3::AsyncBufRead>(reader: R, decoder: D) -> Result<D::Value, ReadError<D::Error>> {
@@ -654,6 +1145,37 @@ pub async fn decode_futures_0_3<D: Decoder + Default>(reader: impl futures_io_0_
     decode_futures_0_3_with(reader, D::default()).await
 }
 
+/// Asynchronously decodes a value of type `T` from the given reader.
+#[cfg(feature = "futures_0_3")]
+pub async fn decode_futures_0_3_value<T: Decode>(reader: impl futures_io_0_3::AsyncBufRead) -> Result<T, ReadError<<T::Decoder as Decoder>::Error>> {
+    decode_futures_0_3_with(reader, T::Decoder::default()).await
+}
+
+/// Asynchronously decodes a stream of values from the given reader.
+///
+/// After each decoded value the decoder is reset using `Default`. The stream ends when the reader
+/// reaches EOF between values. If EOF is reached in the middle of a value the stream returns the
+/// error from [`Decoder::end`]. If the decoder finishes a value without consuming any bytes the
+/// stream returns [`SequenceError::NoProgress`](error::SequenceError::NoProgress) since empty
+/// values would be returned forever. The stream ends after returning any error.
+///
+/// The decoder only learns that a value is complete when it rejects further bytes so a value
+/// ending exactly at the end of the currently available data is only returned once more bytes
+/// arrive or the reader reaches EOF.
+#[cfg(feature = "futures_0_3")]
+pub fn decode_stream_futures_0_3<D: Decoder + Default, R: futures_io_0_3::AsyncBufRead>(reader: R) -> impl futures_core_0_3::Stream<Item = Result<D::Value, ReadError<error::SequenceError<D::Error>>>> {
+    use futures_io_0_3::AsyncBufRead;
+
+    future::DecodeStream {
+        reader,
+        poll_fn: <R as AsyncBufRead>::poll_fill_buf,
+        consume_fn: <R as AsyncBufRead>::consume,
+        decoder: D::default(),
+        in_progress: false,
+        done: false,
+    }
+}
+
 /// Asynchronously decodes a value from the given reader using a custom decoder.
 #[cfg(feature = "tokio")]
 pub async fn decode_tokio_with<D: Decoder, R: tokio::io::AsyncBufRead>(reader: R, decoder: D) -> Result<D::Value, ReadError<D::Error>> {
@@ -674,6 +1196,37 @@ pub async fn decode_tokio<D: Decoder + Default>(reader: impl tokio::io::AsyncBuf
     decode_tokio_with(reader, D::default()).await
 }
 
+/// Asynchronously decodes a value of type `T` from the given reader.
+#[cfg(feature = "tokio")]
+pub async fn decode_tokio_value<T: Decode>(reader: impl tokio::io::AsyncBufRead) -> Result<T, ReadError<<T::Decoder as Decoder>::Error>> {
+    decode_tokio_with(reader, T::Decoder::default()).await
+}
+
+/// Asynchronously decodes a stream of values from the given reader.
+///
+/// After each decoded value the decoder is reset using `Default`. The stream ends when the reader
+/// reaches EOF between values. If EOF is reached in the middle of a value the stream returns the
+/// error from [`Decoder::end`]. If the decoder finishes a value without consuming any bytes the
+/// stream returns [`SequenceError::NoProgress`](error::SequenceError::NoProgress) since empty
+/// values would be returned forever. The stream ends after returning any error.
+///
+/// The decoder only learns that a value is complete when it rejects further bytes so a value
+/// ending exactly at the end of the currently available data is only returned once more bytes
+/// arrive or the reader reaches EOF.
+#[cfg(feature = "tokio")]
+pub fn decode_stream_tokio<D: Decoder + Default, R: tokio::io::AsyncBufRead>(reader: R) -> impl futures_core_0_3::Stream<Item = Result<D::Value, ReadError<error::SequenceError<D::Error>>>> {
+    use tokio::io::AsyncBufRead;
+
+    future::DecodeStream {
+        reader,
+        poll_fn: <R as AsyncBufRead>::poll_fill_buf,
+        consume_fn: <R as AsyncBufRead>::consume,
+        decoder: D::default(),
+        in_progress: false,
+        done: false,
+    }
+}
+
 /// Asynchronously decodes a value from the given reader using a custom decoder.
 #[cfg(feature = "async-std")]
 pub async fn decode_async_std_with<D: Decoder, R: async_std::io::BufRead>(reader: R, decoder: D) -> Result<D::Value, ReadError<D::Error>> {
@@ -694,6 +1247,37 @@ pub async fn decode_async_std<D: Decoder + Default>(reader: impl async_std::io::
     decode_async_std_with(reader, D::default()).await
 }
 
+/// Asynchronously decodes a value of type `T` from the given reader.
+#[cfg(feature = "async-std")]
+pub async fn decode_async_std_value<T: Decode>(reader: impl async_std::io::BufRead) -> Result<T, ReadError<<T::Decoder as Decoder>::Error>> {
+    decode_async_std_with(reader, T::Decoder::default()).await
+}
+
+/// Asynchronously decodes a stream of values from the given reader.
+///
+/// After each decoded value the decoder is reset using `Default`. The stream ends when the reader
+/// reaches EOF between values. If EOF is reached in the middle of a value the stream returns the
+/// error from [`Decoder::end`]. If the decoder finishes a value without consuming any bytes the
+/// stream returns [`SequenceError::NoProgress`](error::SequenceError::NoProgress) since empty
+/// values would be returned forever. The stream ends after returning any error.
+///
+/// The decoder only learns that a value is complete when it rejects further bytes so a value
+/// ending exactly at the end of the currently available data is only returned once more bytes
+/// arrive or the reader reaches EOF.
+#[cfg(feature = "async-std")]
+pub fn decode_stream_async_std<D: Decoder + Default, R: async_std::io::BufRead>(reader: R) -> impl futures_core_0_3::Stream<Item = Result<D::Value, ReadError<error::SequenceError<D::Error>>>> {
+    use async_std::io::BufRead as AsyncBufRead;
+
+    future::DecodeStream {
+        reader,
+        poll_fn: <R as AsyncBufRead>::poll_fill_buf,
+        consume_fn: <R as AsyncBufRead>::consume,
+        decoder: D::default(),
+        in_progress: false,
+        done: false,
+    }
+}
+
 pub async fn encode_for_each_async<F: core::future::Future<Output = ()>>(mut encoder: impl Encoder, mut f: impl FnMut(&[u8]) -> F) {
     while !encoder.encoded_chunk().is_empty() {
         f(encoder.encoded_chunk()).await;
@@ -798,6 +1382,13 @@ impl From<ReadError<core::convert::Infallible, core::convert::Infallible>> for c
     }
 }
 
+#[cfg(feature = "std")]
+impl<E> From<std::io::Error> for ReadError<E, std::io::Error> {
+    fn from(error: std::io::Error) -> Self {
+        ReadError::Read(error)
+    }
+}
+
 #[cfg(feature = "std")]
 impl<E: std::error::Error + Send + Sync + 'static> From<ReadError<E, std::io::Error>> for std::io::Error {
     fn from(error: ReadError<E, std::io::Error>) -> Self {
@@ -828,3 +1419,126 @@ impl<D: std::error::Error + 'static, R: std::error::Error + 'static> std::error:
         }
     }
 }
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod tests {
+    use crate::ReadError;
+    use crate::error::SequenceError;
+    use crate::decoders::{IntDecoder, U8Decoder};
+    use crate::int::BigEndian;
+
+    #[test]
+    fn decode_iter_sync_clean_end() {
+        let mut iter = crate::decode_iter_sync::<U8Decoder, _>(&[1u8, 2][..]);
+        assert_eq!(iter.next().unwrap().unwrap(), 1);
+        assert_eq!(iter.next().unwrap().unwrap(), 2);
+        assert!(iter.next().is_none());
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    fn decode_iter_sync_unexpected_end() {
+        let mut iter = crate::decode_iter_sync::<IntDecoder<u16, BigEndian>, _>(&[0u8, 1, 2][..]);
+        assert_eq!(iter.next().unwrap().unwrap(), 1);
+        assert!(matches!(iter.next(), Some(Err(ReadError::Decode(_)))));
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    fn decode_iter_sync_empty_values() {
+        let mut iter = crate::decode_iter_sync::<crate::decoders::ByteArrayDecoder<0>, _>(&[1u8, 2][..]);
+        assert!(matches!(iter.next(), Some(Err(ReadError::Decode(SequenceError::NoProgress)))));
+        assert!(iter.next().is_none());
+
+        let mut iter = crate::decode_iter_sync::<crate::decoders::ByteArrayDecoder<0>, _>(&[][..]);
+        assert!(iter.next().is_none());
+    }
+
+    #[cfg(feature = "lgio")]
+    #[test]
+    fn decode_iter_sync_lgio() {
+        let mut iter = crate::decode_iter_sync_lgio::<U8Decoder, _>(&[1u8, 2][..]);
+        assert_eq!(iter.next().unwrap().unwrap(), 1);
+        assert_eq!(iter.next().unwrap().unwrap(), 2);
+        assert!(iter.next().is_none());
+
+        let mut iter = crate::decode_iter_sync_lgio::<IntDecoder<u16, BigEndian>, _>(&[0u8, 1, 2][..]);
+        assert_eq!(iter.next().unwrap().unwrap(), 1);
+        assert!(matches!(iter.next(), Some(Err(ReadError::Decode(_)))));
+        assert!(iter.next().is_none());
+
+        let mut iter = crate::decode_iter_sync_lgio::<crate::decoders::ByteArrayDecoder<0>, _>(&[1u8, 2][..]);
+        assert!(matches!(iter.next(), Some(Err(ReadError::Decode(SequenceError::NoProgress)))));
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    fn write_all_vectored() {
+        use crate::Encoder;
+        use crate::encoders::BytesEncoder;
+
+        // Accepts at most 3 bytes per write to exercise partial writes across chunks.
+        struct Writer {
+            buf: alloc::vec::Vec<u8>,
+            writes: usize,
+        }
+
+        impl std::io::Write for Writer {
+            fn write(&mut self, _: &[u8]) -> std::io::Result<usize> {
+                panic!("non-vectored write");
+            }
+
+            fn write_vectored(&mut self, bufs: &[std::io::IoSlice]) -> std::io::Result<usize> {
+                self.writes += 1;
+                let mut total = 0;
+                for buf in bufs {
+                    let to_write = buf.len().min(3 - total);
+                    self.buf.extend_from_slice(&buf[..to_write]);
+                    total += to_write;
+                }
+                Ok(total)
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let encoder = BytesEncoder::new([1, 2]).chain(BytesEncoder::new([3, 4])).chain(BytesEncoder::new([5]));
+        let mut writer = Writer { buf: alloc::vec::Vec::new(), writes: 0 };
+        encoder.write_all_vectored_sync(&mut writer).unwrap();
+        assert_eq!(writer.buf, [1, 2, 3, 4, 5]);
+        assert_eq!(writer.writes, 2);
+    }
+
+    #[test]
+    fn assume_buffered_vectored() {
+        use crate::Encoder;
+        use crate::encoders::BytesEncoder;
+
+        struct Writer {
+            slices: alloc::vec::Vec<usize>,
+        }
+
+        impl std::io::Write for Writer {
+            fn write(&mut self, _: &[u8]) -> std::io::Result<usize> {
+                panic!("non-vectored write");
+            }
+
+            fn write_vectored(&mut self, bufs: &[std::io::IoSlice]) -> std::io::Result<usize> {
+                self.slices.push(bufs.len());
+                Ok(bufs.iter().map(|buf| buf.len()).sum())
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let encoder = BytesEncoder::new([1, 2]).chain(BytesEncoder::new([3, 4])).chain(BytesEncoder::new([5]));
+        let mut writer = Writer { slices: alloc::vec::Vec::new() };
+        encoder.write_all_vectored_sync(crate::AssumeBuffered::new(&mut writer)).unwrap();
+        assert_eq!(writer.slices, [3]);
+    }
+}
@@ -21,6 +21,119 @@ macro_rules! mapped_decoder {
     }
 }
 
+/// Creates an enum of variant decoders to be used with [`Tagged`](crate::decoders::Tagged).
+///
+/// Each arm maps the tag pattern to the variant name, its decoder and a function converting the
+/// decoded value into the resulting enum. The decoders are created using `Default`. The macro
+/// also creates the error enum with one variant per decoder which implements `std::error::Error`
+/// if the `std` feature is enabled.
+///
+/// ```
+/// use push_decode::decoders::{ByteArrayDecoder, Tagged, U8Decoder, U32BeDecoder};
+///
+/// pub enum Message {
+///     Ping(u32),
+///     Stop,
+/// }
+///
+/// push_decode::tagged_decoder! {
+///     pub enum MessageVariants(MessageVariantsError): U8Decoder => Message {
+///         0 => Ping(U32BeDecoder) => Message::Ping,
+///         1 => Stop(ByteArrayDecoder<0>) => |_| Message::Stop,
+///     }
+/// }
+///
+/// pub type MessageDecoder = Tagged<MessageVariants>;
+/// ```
+#[macro_export]
+macro_rules! tagged_decoder {
+    ($(#[$($attr:tt)*])* $vis:vis enum $name:ident($error:ident): $tag_decoder:ty => $value:ty { $($tag:pat => $variant:ident($decoder:ty) => $func:expr),+ $(,)? }) => {
+        $(#[$($attr)*])*
+        $vis enum $name {
+            $($variant($decoder),)+
+        }
+
+        /// Error returned when decoding a variant fails.
+        #[derive(Debug)]
+        $vis enum $error {
+            $($variant(<$decoder as $crate::Decoder>::Error),)+
+        }
+
+        impl ::core::fmt::Display for $error {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                match *self {
+                    $($error::$variant(_) => f.write_str(concat!("failed to decode variant `", stringify!($variant), "`")),)+
+                }
+            }
+        }
+
+        $crate::__std_only! {
+            impl $crate::__private::StdError for $error where $(for<'a> <$decoder as $crate::Decoder>::Error: $crate::__private::StdError + 'static),+ {
+                fn source(&self) -> ::core::option::Option<&(dyn $crate::__private::StdError + 'static)> {
+                    match *self {
+                        $($error::$variant(ref error) => ::core::option::Option::Some(error),)+
+                    }
+                }
+            }
+        }
+
+        impl $crate::Decoder for $name {
+            type Value = $value;
+            type Error = $error;
+
+            fn decode_chunk(&mut self, bytes: &mut &[u8]) -> ::core::result::Result<(), Self::Error> {
+                match self {
+                    $($name::$variant(decoder) => $crate::Decoder::decode_chunk(decoder, bytes).map_err($error::$variant),)+
+                }
+            }
+
+            fn end(self) -> ::core::result::Result<Self::Value, Self::Error> {
+                match self {
+                    $($name::$variant(decoder) => $crate::Decoder::end(decoder).map($func).map_err($error::$variant),)+
+                }
+            }
+        }
+
+        // The higher-ranked bounds make the impl conditional instead of failing to compile if
+        // some decoder doesn't implement the trait.
+        impl $crate::KnownMinLenDecoder for $name where $(for<'a> $decoder: $crate::KnownMinLenDecoder),+ {
+            fn min_required_bytes(&self) -> usize {
+                match self {
+                    $($name::$variant(decoder) => $crate::KnownMinLenDecoder::min_required_bytes(decoder),)+
+                }
+            }
+        }
+
+        impl $crate::decoders::Variants for $name {
+            type TagDecoder = $tag_decoder;
+
+            fn from_tag(tag: <$tag_decoder as $crate::Decoder>::Value) -> ::core::result::Result<Self, <$tag_decoder as $crate::Decoder>::Value> {
+                match tag {
+                    $($tag => ::core::result::Result::Ok($name::$variant(::core::default::Default::default())),)+
+                    #[allow(unreachable_patterns)]
+                    tag => ::core::result::Result::Err(tag),
+                }
+            }
+        }
+    }
+}
+
+// The generated code can't check the features of this crate so the items requiring `std` are
+// passed through this macro.
+#[cfg(feature = "std")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __std_only {
+    ($($item:item)*) => { $($item)* }
+}
+
+#[cfg(not(feature = "std"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __std_only {
+    ($($item:item)*) => {}
+}
+
 #[macro_export]
 macro_rules! delegate {
     (impl$(<$($gen:ident $(: $gen_bounds:path)?),*>)? Encoder for $ty:ty $(where $($where_ty:ty: $($where_bound:path)?),*)? { $field:tt }) => {
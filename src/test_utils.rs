@@ -0,0 +1,58 @@
+//! Helpers shared by the tests of multiple modules.
+
+#[cfg(feature = "alloc")]
+use crate::{Encoder, ExactSizeEncoder};
+use crate::checksum::Checksum;
+
+/// Encodes all bytes produced by the encoder into a `Vec`.
+#[cfg(feature = "alloc")]
+pub(crate) fn encode<E: Encoder>(encoder: E) -> alloc::vec::Vec<u8> {
+    let mut buf = alloc::vec::Vec::new();
+    encoder.write_to_vec(&mut buf);
+    buf
+}
+
+/// Sum of all bytes, optionally observes the bytes it was given.
+#[derive(Default)]
+pub(crate) struct Sum<'a>(pub(crate) u16, pub(crate) Option<&'a mut [u8; 16]>, pub(crate) usize);
+
+impl Checksum for Sum<'_> {
+    type Digest = [u8; 2];
+    const LEN: usize = 2;
+
+    fn update(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 = self.0.wrapping_add(u16::from(*byte));
+        }
+        if let Some(observed) = &mut self.1 {
+            observed[self.2..(self.2 + bytes.len())].copy_from_slice(bytes);
+        }
+        self.2 += bytes.len();
+    }
+
+    fn finalize(self) -> Self::Digest {
+        self.0.to_be_bytes()
+    }
+}
+
+/// Encoder claiming to have `usize::MAX` bytes to test overflows.
+#[cfg(feature = "alloc")]
+pub(crate) struct HugeEncoder;
+
+#[cfg(feature = "alloc")]
+impl Encoder for HugeEncoder {
+    fn encoded_chunk(&self) -> &[u8] {
+        &[0]
+    }
+
+    fn next(&mut self) -> bool {
+        false
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl ExactSizeEncoder for HugeEncoder {
+    fn encoded_len(&self) -> usize {
+        usize::MAX
+    }
+}